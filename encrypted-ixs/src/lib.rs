@@ -0,0 +1,175 @@
+use arcis_imports::*;
+
+#[encrypted]
+mod circuits {
+    use arcis_imports::*;
+
+    /// Bid slots per computation, matching `MAX_BIDS_PER_AUCTION`
+    const MAX_BIDS: usize = 16;
+
+    /// A bid as encrypted by the bidder
+    pub struct SealedBid {
+        // Price per unit
+        price: u64,
+        // Units wanted, 1 in single-item auctions
+        quantity: u64,
+        // Authentication tag over the auction and bidder (`bid_tag`)
+        tag: u128,
+    }
+
+    /// Plaintext terms the program passes for each bid slot
+    pub struct BidTerms {
+        // Tag the bid must decrypt to
+        tag: u128,
+        // Most the bid may commit to pay: its deposit when fully funded
+        max_payment: u64,
+    }
+
+    /// Plaintext terms of the auction
+    pub struct AuctionTerms {
+        // Number of filled bid slots, the rest are padding
+        bid_count: u8,
+        min_bid: u64,
+        // Units for sale, 1 in single-item auctions
+        quantity: u64,
+        has_reserve: bool,
+        // Whether the winner pays the second price
+        second_price: bool,
+    }
+
+    /// Revealed result of the auction, decoded by `ComputationResult::from_outcome`
+    pub struct Outcome {
+        reserve_met: bool,
+        has_winner: bool,
+        winner: u8,
+        winning_bid: u64,
+        // Whether `second_bid` is set, either by a runner-up or the reserve
+        has_second: bool,
+        runner_up: u8,
+        second_bid: u64,
+        clearing_price: u64,
+        allocations: [u64; MAX_BIDS],
+        rejected_bids: u8,
+        // Whether `runner_up` placed a second eligible bid
+        has_runner_up: bool,
+    }
+
+    /// Rank the sealed bids of an auction
+    ///
+    /// A bid is eligible when its tag verifies, its price meets `min_bid`
+    /// and the reserve, its quantity is available and its payment is
+    /// covered. Eligible bids are ranked by price with ties going to the
+    /// lower slot, which the program fills in submission order. Units are
+    /// allocated down the ranking, and the lowest allocated price is the
+    /// uniform clearing price.
+    ///
+    /// In second-price auctions with a reserve, the second price is floored
+    /// at the reserve, so a lone eligible bid pays the reserve rather than
+    /// `min_bid`. The winner's payment then reveals the reserve.
+    #[instruction]
+    pub fn finalize_auction(
+        bids: [Enc<Shared, SealedBid>; MAX_BIDS],
+        terms: [BidTerms; MAX_BIDS],
+        auction: AuctionTerms,
+        reserve: Enc<Shared, u64>,
+    ) -> Outcome {
+        let reserve = reserve.to_arcis();
+
+        let mut prices = [0u64; MAX_BIDS];
+        let mut quantities = [0u64; MAX_BIDS];
+        let mut eligible = [false; MAX_BIDS];
+        let mut rejected_bids = 0u8;
+        for i in 0..MAX_BIDS {
+            let bid = bids[i].to_arcis();
+            let filled = (i as u8) < auction.bid_count;
+            let authentic = bid.tag == terms[i].tag;
+            let payment = (bid.price as u128) * (bid.quantity as u128);
+            let valid = bid.price >= auction.min_bid
+                && bid.quantity >= 1
+                && bid.quantity <= auction.quantity
+                && payment <= terms[i].max_payment as u128
+                && (!auction.has_reserve || bid.price >= reserve);
+            if filled && !authentic {
+                rejected_bids += 1;
+            }
+            prices[i] = bid.price;
+            quantities[i] = bid.quantity;
+            eligible[i] = filled && authentic && valid;
+        }
+
+        let mut has_winner = false;
+        let mut winner = 0u8;
+        let mut winning_bid = 0u64;
+        let mut has_second = false;
+        let mut runner_up = 0u8;
+        let mut second_bid = 0u64;
+        for i in 0..MAX_BIDS {
+            if eligible[i] {
+                if !has_winner || prices[i] > winning_bid {
+                    has_second = has_winner;
+                    runner_up = winner;
+                    second_bid = winning_bid;
+                    has_winner = true;
+                    winner = i as u8;
+                    winning_bid = prices[i];
+                } else if !has_second || prices[i] > second_bid {
+                    has_second = true;
+                    runner_up = i as u8;
+                    second_bid = prices[i];
+                }
+            }
+        }
+
+        let has_runner_up = has_second;
+        if auction.second_price && auction.has_reserve && has_winner {
+            if !has_second || second_bid < reserve {
+                second_bid = reserve;
+            }
+            has_second = true;
+        }
+
+        let mut allocations = [0u64; MAX_BIDS];
+        let mut clearing_price = 0u64;
+        for i in 0..MAX_BIDS {
+            let mut ahead = 0u64;
+            for j in 0..MAX_BIDS {
+                let better = prices[j] > prices[i] || (prices[j] == prices[i] && j < i);
+                if eligible[j] && better {
+                    ahead += quantities[j];
+                }
+            }
+            let taken = if ahead < auction.quantity {
+                ahead
+            } else {
+                auction.quantity
+            };
+            let remaining = auction.quantity - taken;
+            let units = if quantities[i] < remaining {
+                quantities[i]
+            } else {
+                remaining
+            };
+            if eligible[i] && units > 0 {
+                allocations[i] = units;
+                if clearing_price == 0 || prices[i] < clearing_price {
+                    clearing_price = prices[i];
+                }
+            }
+        }
+
+        Outcome {
+            reserve_met: !auction.has_reserve || has_winner,
+            has_winner,
+            winner,
+            winning_bid,
+            has_second,
+            runner_up,
+            second_bid,
+            clearing_price,
+            allocations,
+            rejected_bids,
+            has_runner_up,
+        }
+        .reveal()
+    }
+}
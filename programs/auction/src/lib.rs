@@ -1,9 +1,21 @@
+// Anchor's generated IDL instructions still call the deprecated
+// `AccountInfo::realloc`
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::system_program;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::metadata::mpl_token_metadata::types::Creator;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 declare_id!("AucBLdAuct1on11111111111111111111111111111");
 
-#[program]
+#[arcium_program]
 pub mod auction {
     use super::*;
 
@@ -15,43 +27,300 @@ pub mod auction {
     /// - End timestamp
     /// - Creator's public key
     /// - Arcium MXE public key for encryption
+    /// - Collateral requirement for bids
+    ///
+    /// The MXE public key is copied from an active `MxeCluster` in the
+    /// admin-curated registry, so creators cannot substitute a key they
+    /// control. Commit-reveal auctions do not use a cluster.
+    ///
+    /// A program-owned vault PDA is created alongside the auction to hold
+    /// bid deposits until the auction is settled. When a `quote_mint` account
+    /// is passed the auction is denominated in that SPL token instead, and
+    /// deposits are held in the auction's associated token account.
+    ///
+    /// The auction PDA is addressed by `(creator, index)`, where `index` is
+    /// the creator's auction counter kept in their `CreatorProfile`. Clients
+    /// can enumerate a creator's auctions by walking indices up to
+    /// `auction_count`.
+    ///
+    /// Auctions with a future `start_time` are created as `Upcoming` and
+    /// open for bids once that time is reached.
+    ///
+    /// When an `asset_mint` account is passed the auction is asset-backed:
+    /// `asset_amount` units are moved from the creator into an auction-owned
+    /// vault and only leave it through settlement or cancellation.
+    ///
+    /// Metadata length and auction duration are bounded by the protocol
+    /// `Config`, and its current fee is fixed on the auction at creation.
+    ///
+    /// Sale proceeds go to the creator unless `revenue_shares` splits them
+    /// across up to `MAX_REVENUE_SHARES` recipients. When no split is given
+    /// and the asset's token metadata account is passed, the split defaults
+    /// to the verified creators listed in that metadata, rescaled to their
+    /// combined share.
+    ///
+    /// Fixed-collateral first- and second-price auctions can set a
+    /// `payment_window`, making bids partially funded: the collateral no
+    /// longer caps the bid, and a winner whose price exceeds it has that
+    /// long after finalization to pay the balance (see `claim_default`).
+    ///
+    /// An optional `keeper_bounty` in lamports is locked in the vault and
+    /// paid to whoever triggers finalization once bidding has closed.
+    ///
+    /// `max_bids` caps the number of open bids at `MAX_BIDS_PER_AUCTION`
+    /// or less, so every bid fits in the single transaction that finalizes
+    /// the auction.
+    ///
+    /// Passing an `allowlist_root` makes the auction private: only wallets
+    /// in the Merkle tree (see `allowlist_leaf`) can bid. A `bid_gate`
+    /// additionally restricts bidding to holders of a token or collection.
     pub fn create_auction(
         ctx: Context<CreateAuction>,
-        item_name: String,
-        description: String,
-        min_bid: u64,
-        end_time: i64,
-        arcium_mxe_pubkey: [u8; 32], // Arcium cluster public key for encryption
+        params: CreateAuctionParams,
     ) -> Result<()> {
         let auction = &mut ctx.accounts.auction;
+        let config = &ctx.accounts.config;
         let clock = Clock::get()?;
 
+        let start_time = params
+            .start_time
+            .unwrap_or(clock.unix_timestamp)
+            .max(clock.unix_timestamp);
+
         require!(
-            end_time > clock.unix_timestamp,
+            params.end_time > start_time,
             AuctionError::InvalidEndTime
         );
-        require!(min_bid > 0, AuctionError::InvalidMinBid);
         require!(
-            item_name.len() <= 64,
+            config.is_valid_duration(params.end_time - start_time),
+            AuctionError::InvalidDuration
+        );
+        require!(params.min_bid > 0, AuctionError::InvalidMinBid);
+        require!(
+            params.max_bids > 0 && params.max_bids <= MAX_BIDS_PER_AUCTION,
+            AuctionError::InvalidMaxBids
+        );
+        require!(
+            params.item_name.len() <= config.max_item_name_len as usize,
             AuctionError::ItemNameTooLong
         );
         require!(
-            description.len() <= 256,
+            params.description.len() <= config.max_description_len as usize,
             AuctionError::DescriptionTooLong
         );
+        require!(
+            params.collateral == 0 || params.collateral >= params.min_bid,
+            AuctionError::InvalidCollateral
+        );
+        require!(
+            ctx.accounts.quote_mint.is_some() == ctx.accounts.quote_vault.is_some(),
+            AuctionError::MissingTokenAccount
+        );
+
+        let cluster = match params.kind {
+            AuctionKind::CommitReveal => None,
+            _ => {
+                let cluster = ctx
+                    .accounts
+                    .cluster
+                    .as_ref()
+                    .ok_or(AuctionError::MissingCluster)?;
+                require!(cluster.active, AuctionError::ClusterInactive);
+                Some(cluster)
+            }
+        };
+
+        let quantity = match params.kind {
+            AuctionKind::UniformPrice => params.quantity,
+            _ => 1,
+        };
+        require!(quantity > 0, AuctionError::InvalidQuantity);
+
+        let reveal_end_time = match params.kind {
+            AuctionKind::CommitReveal => params.reveal_end_time,
+            _ => None,
+        };
+        if params.kind == AuctionKind::CommitReveal {
+            require!(
+                reveal_end_time.is_some_and(|reveal_end| reveal_end > params.end_time),
+                AuctionError::InvalidRevealEndTime
+            );
+            // Reveals are settled on-chain, so there is no MPC to check a reserve
+            require!(
+                params.encrypted_reserve.is_none(),
+                AuctionError::ReserveNotSupported
+            );
+        }
+        require!(
+            params.extension_window >= 0
+                && params.extension_duration >= 0
+                && params.max_extension >= 0,
+            AuctionError::InvalidSoftClose
+        );
+        if params.extension_window > 0 {
+            require!(
+                params.extension_duration > 0 && params.max_extension > 0,
+                AuctionError::InvalidSoftClose
+            );
+        }
+        require!(params.payment_window >= 0, AuctionError::InvalidPaymentWindow);
+        if params.payment_window > 0 {
+            require!(
+                params.collateral > 0
+                    && matches!(params.kind, AuctionKind::FirstPrice | AuctionKind::SecondPrice),
+                AuctionError::InvalidPaymentWindow
+            );
+        }
+        if let Some(BidGate::Token { min_amount, .. }) = params.bid_gate {
+            require!(min_amount > 0, AuctionError::InvalidBidGate);
+        }
+
+        let profile = &mut ctx.accounts.creator_profile;
+        profile.creator = ctx.accounts.creator.key();
+        profile.bump = ctx.bumps.creator_profile;
 
         auction.creator = ctx.accounts.creator.key();
-        auction.item_name = item_name;
-        auction.description = description;
-        auction.min_bid = min_bid;
-        auction.end_time = end_time;
+        auction.index = profile.auction_count;
+        auction.item_name = params.item_name;
+        auction.description = params.description;
+        auction.min_bid = params.min_bid;
+        auction.end_time = params.end_time;
         auction.created_at = clock.unix_timestamp;
-        auction.status = AuctionStatus::Active;
+        auction.start_time = start_time;
+        auction.status = if start_time > clock.unix_timestamp {
+            AuctionStatus::Upcoming
+        } else {
+            AuctionStatus::Active
+        };
         auction.bid_count = 0;
-        auction.arcium_mxe_pubkey = arcium_mxe_pubkey;
+        auction.max_bids = params.max_bids;
+        auction.arcium_mxe_pubkey = cluster.map(|c| c.mxe_pubkey).unwrap_or_default();
+        auction.cluster_id = cluster.map(|c| c.cluster_id);
+        auction.collateral = params.collateral;
+        auction.kind = params.kind;
+        auction.quantity = quantity;
+        auction.units_sold = 0;
+        auction.reveal_end_time = reveal_end_time;
+        auction.forfeited_deposits = 0;
+        auction.forfeits_claimed = false;
+        auction.extension_window = params.extension_window;
+        auction.extension_duration = params.extension_duration;
+        auction.max_extension = params.max_extension;
+        auction.extended_by = 0;
+        auction.allow_withdrawals = params.allow_withdrawals;
+        auction.fee_bps = config.fee_bps;
+        auction.keeper_fee_bps = config.keeper_fee_bps;
+        auction.keeper_bounty = params.keeper_bounty;
+        auction.keeper = None;
+        auction.payment_window = params.payment_window;
+        auction.default_policy = params.default_policy;
+        auction.payment_deadline = None;
+        auction.runner_up = None;
+        auction.runner_up_bid = None;
+        auction.revenue_shares = params.revenue_shares;
+        auction.frozen = false;
+        auction.allowlist_root = params.allowlist_root;
+        auction.bid_gate = params.bid_gate;
+        auction.encrypted_reserve = params.encrypted_reserve;
+        auction.quote_mint = ctx.accounts.quote_mint.as_ref().map(|mint| mint.key());
+        auction.proceeds_claimed = false;
+        auction.asset_mint = None;
+        auction.asset_amount = 0;
+        auction.asset_released = false;
         auction.bump = ctx.bumps.auction;
 
+        // Escrow the auctioned asset, if any
+        if let Some(asset_mint) = &ctx.accounts.asset_mint {
+            require!(
+                auction.quote_mint != Some(asset_mint.key()),
+                AuctionError::AssetMintIsQuoteMint
+            );
+            require!(
+                params.asset_amount > 0 && params.asset_amount.checked_rem(quantity) == Some(0),
+                AuctionError::InvalidAssetAmount
+            );
+
+            let asset_vault = ctx
+                .accounts
+                .asset_vault
+                .as_ref()
+                .ok_or(AuctionError::MissingTokenAccount)?;
+            let creator_asset_account = ctx
+                .accounts
+                .creator_asset_account
+                .as_ref()
+                .ok_or(AuctionError::MissingTokenAccount)?;
+
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: creator_asset_account.to_account_info(),
+                        to: asset_vault.to_account_info(),
+                        authority: ctx.accounts.creator.to_account_info(),
+                    },
+                ),
+                params.asset_amount,
+            )?;
+
+            auction.asset_mint = Some(asset_mint.key());
+            auction.asset_amount = params.asset_amount;
+
+            // Default the revenue split to the NFT's verified metadata creators
+            if let Some(metadata) = &ctx.accounts.asset_metadata {
+                require!(
+                    metadata.mint == asset_mint.key(),
+                    AuctionError::InvalidAssetMetadata
+                );
+                if auction.revenue_shares.is_empty() {
+                    auction.revenue_shares =
+                        verified_creator_shares(metadata.creators.as_deref().unwrap_or_default());
+                }
+            }
+        }
+        require!(
+            ctx.accounts.asset_mint.is_some() || ctx.accounts.asset_metadata.is_none(),
+            AuctionError::InvalidAssetMetadata
+        );
+        validate_revenue_shares(&auction.revenue_shares)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.auction = auction.key();
+        vault.bump = ctx.bumps.vault;
+
+        if params.keeper_bounty > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: vault.to_account_info(),
+                    },
+                ),
+                params.keeper_bounty,
+            )?;
+        }
+
+        profile.auction_count = profile
+            .auction_count
+            .checked_add(1)
+            .ok_or(AuctionError::AuctionCountOverflow)?;
+
         msg!("Auction created with Arcium MXE pubkey");
+        emit!(AuctionCreated {
+            auction: auction.key(),
+            creator: auction.creator,
+            index: auction.index,
+            kind: auction.kind,
+            min_bid: auction.min_bid,
+            quantity: auction.quantity,
+            max_bids: auction.max_bids,
+            quote_mint: auction.quote_mint,
+            asset_mint: auction.asset_mint,
+            start_time: auction.start_time,
+            end_time: auction.end_time,
+            timestamp: clock.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -63,29 +332,68 @@ pub mod auction {
     /// 
     /// Only the encrypted data is stored on-chain. The actual bid amount
     /// remains hidden until MPC computation reveals the winner.
-    pub fn submit_bid(
-        ctx: Context<SubmitBid>,
+    ///
+    /// The ciphertext carries an authentication tag over the auction and
+    /// bidder keys (see `bid_associated_data`), so the MPC rejects a bid
+    /// copied into another auction or under another wallet. Each
+    /// (auction, x25519 key) pair is tracked in a `NonceRegistry` that
+    /// rejects nonce reuse and binds the key to the first wallet using it.
+    ///
+    /// Every bid locks a deposit in the auction vault. If the auction has a
+    /// fixed collateral the deposit must equal it, otherwise the bidder picks
+    /// any deposit of at least `min_bid`. A bid larger than its deposit can
+    /// never win, so over-depositing is how bidders hide their bid size.
+    /// Auctions with a `payment_window` are the exception: there the
+    /// collateral only secures the bid and the winner pays the balance.
+    ///
+    /// In commit-reveal auctions `encrypted_bid_data` is the bid commitment
+    /// (see `bid_commitment`) and the x25519 key and nonce are unused.
+    ///
+    /// With a soft close configured, a bid landing within `extension_window`
+    /// of `end_time` pushes the end out by `extension_duration`, up to
+    /// `max_extension` in total, and emits `AuctionExtended`.
+    ///
+    /// Private auctions require `allowlist_proof`, the Merkle proof of the
+    /// bidder's wallet against the auction's allowlist root. It is ignored
+    /// for public auctions.
+    ///
+    /// Token-gated auctions check the bidder's holdings against accounts
+    /// passed in `remaining_accounts` (see `check_bid_gate`).
+    pub fn submit_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitBid<'info>>,
         encrypted_bid_data: Vec<u8>, // Rescue cipher output
         bidder_pubkey: [u8; 32],      // Ephemeral x25519 public key
         nonce: [u8; 16],               // Encryption nonce
+        deposit: u64,                  // Amount locked in escrow, in quote units
+        allowlist_proof: Vec<[u8; 32]>, // Merkle proof for private auctions
     ) -> Result<()> {
         let auction = &mut ctx.accounts.auction;
         let bid = &mut ctx.accounts.bid;
         let clock = Clock::get()?;
 
-        // Validate auction is still active
-        require!(
-            auction.status == AuctionStatus::Active,
-            AuctionError::AuctionNotActive
-        );
-        require!(
-            clock.unix_timestamp < auction.end_time,
-            AuctionError::AuctionEnded
-        );
+        // Validate auction is open for bids
+        auction.require_accepting_bids(clock.unix_timestamp)?;
+        require!(auction.bid_count < auction.max_bids, AuctionError::TooManyBids);
+
+        // Private auctions only accept allowlisted wallets
+        if let Some(root) = auction.allowlist_root {
+            require!(
+                allowlist_proof.len() <= MAX_ALLOWLIST_PROOF_LEN
+                    && verify_allowlist_proof(
+                        &root,
+                        &allowlist_leaf(&ctx.accounts.bidder.key()),
+                        &allowlist_proof
+                    ),
+                AuctionError::NotAllowlisted
+            );
+        }
+        if let Some(gate) = &auction.bid_gate {
+            check_bid_gate(gate, &ctx.accounts.bidder.key(), ctx.remaining_accounts)?;
+        }
 
         // Validate encrypted data
         require!(
-            encrypted_bid_data.len() == 32,
+            encrypted_bid_data.len() == auction.kind.bid_ciphertext_len(),
             AuctionError::InvalidEncryptedData
         );
         require!(
@@ -97,6 +405,49 @@ pub mod auction {
             AuctionError::InvalidNonce
         );
 
+        if auction.kind != AuctionKind::CommitReveal {
+            ctx.accounts
+                .nonce_registry
+                .as_mut()
+                .ok_or(AuctionError::MissingNonceRegistry)?
+                .record(
+                    auction.key(),
+                    ctx.accounts.bidder.key(),
+                    bidder_pubkey,
+                    nonce,
+                    ctx.bumps
+                        .nonce_registry
+                        .ok_or(AuctionError::MissingNonceRegistry)?,
+                )?;
+        }
+
+        // Validate deposit against the auction's collateral rules
+        if auction.collateral > 0 {
+            require!(
+                deposit == auction.collateral,
+                AuctionError::InvalidDeposit
+            );
+        } else {
+            require!(
+                deposit >= auction.min_bid,
+                AuctionError::InvalidDeposit
+            );
+        }
+
+        // Lock the deposit in escrow
+        Escrow::new(
+            auction,
+            &ctx.accounts.vault,
+            ctx.accounts.quote_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?
+        .deposit(
+            &ctx.accounts.bidder,
+            ctx.accounts.bidder_token_account.as_ref(),
+            &ctx.accounts.system_program,
+            deposit,
+        )?;
+
         // Store encrypted bid
         bid.auction = auction.key();
         bid.bidder = ctx.accounts.bidder.key();
@@ -104,37 +455,191 @@ pub mod auction {
         bid.x25519_pubkey = bidder_pubkey;
         bid.nonce = nonce;
         bid.timestamp = clock.unix_timestamp;
+        bid.deposit = deposit;
+        bid.settled = false;
+        bid.revealed_amount = None;
+        bid.revision = 0;
         bid.bump = ctx.bumps.bid;
 
         // Increment auction bid count
         auction.bid_count = auction.bid_count.checked_add(1).unwrap();
 
+        // Soft close: late bids push the end time out
+        if let Some(previous_end_time) = auction.apply_soft_close(clock.unix_timestamp) {
+            emit!(AuctionExtended {
+                auction: auction.key(),
+                bidder: ctx.accounts.bidder.key(),
+                previous_end_time,
+                new_end_time: auction.end_time,
+                total_extension: auction.extended_by,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
         msg!(
             "Encrypted bid submitted - Bidder: {}, Auction: {}",
             ctx.accounts.bidder.key(),
             auction.key()
         );
+        emit!(BidSubmitted {
+            auction: auction.key(),
+            bidder: bid.bidder,
+            deposit,
+            bid_count: auction.bid_count,
+            end_time: auction.end_time,
+            timestamp: clock.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    /// Finalize auction and reveal winner via Arcium MPC
-    /// 
-    /// This instruction would normally trigger:
-    /// 1. Arcium MXE nodes fetch all encrypted bids
-    /// 2. MPC computation determines winner without decrypting individual bids
-    /// 3. Callback instruction writes winner data on-chain
-    /// 
-    /// For demo: We store the MPC computation request and result
-    pub fn finalize_auction(
-        ctx: Context<FinalizeAuction>,
-        winner_pubkey: Pubkey,
-        winning_bid_amount: u64,
-        mpc_computation_id: String,
+    /// Replace an existing bid in place before `end_time`
+    ///
+    /// Each wallet holds a single bid per auction. Updating swaps the
+    /// ciphertext, x25519 key and nonce (or the commitment in commit-reveal
+    /// auctions) and bumps the bid's revision counter. The deposit is left
+    /// unchanged. Late updates count toward the soft close like new bids.
+    ///
+    /// The new key and nonce go through the same `NonceRegistry` checks as
+    /// `submit_bid`; a reused key needs a higher nonce than its last one.
+    pub fn update_bid(
+        ctx: Context<UpdateBid>,
+        encrypted_bid_data: Vec<u8>, // Rescue cipher output
+        bidder_pubkey: [u8; 32],      // Ephemeral x25519 public key
+        nonce: [u8; 16],               // Encryption nonce
+    ) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let bid = &mut ctx.accounts.bid;
+        let clock = Clock::get()?;
+
+        auction.require_accepting_bids(clock.unix_timestamp)?;
+        require!(
+            encrypted_bid_data.len() == auction.kind.bid_ciphertext_len(),
+            AuctionError::InvalidEncryptedData
+        );
+        if auction.kind != AuctionKind::CommitReveal {
+            ctx.accounts
+                .nonce_registry
+                .as_mut()
+                .ok_or(AuctionError::MissingNonceRegistry)?
+                .record(
+                    auction.key(),
+                    bid.bidder,
+                    bidder_pubkey,
+                    nonce,
+                    ctx.bumps
+                        .nonce_registry
+                        .ok_or(AuctionError::MissingNonceRegistry)?,
+                )?;
+        }
+
+        bid.encrypted_data = encrypted_bid_data;
+        bid.x25519_pubkey = bidder_pubkey;
+        bid.nonce = nonce;
+        bid.timestamp = clock.unix_timestamp;
+        bid.revision = bid
+            .revision
+            .checked_add(1)
+            .ok_or(AuctionError::RevisionOverflow)?;
+
+        if let Some(previous_end_time) = auction.apply_soft_close(clock.unix_timestamp) {
+            emit!(AuctionExtended {
+                auction: auction.key(),
+                bidder: bid.bidder,
+                previous_end_time,
+                new_end_time: auction.end_time,
+                total_extension: auction.extended_by,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        msg!(
+            "Encrypted bid updated - Bidder: {}, Revision: {}",
+            bid.bidder,
+            bid.revision
+        );
+        emit!(BidUpdated {
+            auction: auction.key(),
+            bidder: bid.bidder,
+            revision: bid.revision,
+            end_time: auction.end_time,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Retract a bid while the auction is still active
+    ///
+    /// Only allowed when the creator enabled `allow_withdrawals`. The
+    /// deposit is refunded, the `Bid` account is closed to return its rent,
+    /// and `bid_count` is decremented so `cancel_auction` and the
+    /// finalization bid set stay consistent.
+    pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let bid = &ctx.accounts.bid;
+        let clock = Clock::get()?;
+
+        require!(auction.allow_withdrawals, AuctionError::WithdrawalsDisabled);
+        auction.require_accepting_bids(clock.unix_timestamp)?;
+
+        Escrow::new(
+            auction,
+            &ctx.accounts.vault,
+            ctx.accounts.quote_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?
+        .release(
+            &ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.bidder_token_account.as_ref(),
+            bid.deposit,
+        )?;
+
+        auction.bid_count = auction.bid_count.checked_sub(1).unwrap();
+
+        msg!(
+            "Bid withdrawn - Bidder: {}, Auction: {}",
+            bid.bidder,
+            auction.key()
+        );
+        emit!(BidWithdrawn {
+            auction: auction.key(),
+            bidder: bid.bidder,
+            refund: bid.deposit,
+            bid_count: auction.bid_count,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Queue the Arcium MPC computation that determines the winner
+    ///
+    /// Can be called once the auction has ended. Every `Bid` account of the
+    /// auction must be passed in `remaining_accounts`. The bids fill the
+    /// slots of the `finalize_auction` circuit in submission order, and the
+    /// MXE nodes read each ciphertext straight from its account and compare
+    /// them without decrypting individual bids (see `finalize_auction_args`).
+    /// The slots' bidders and deposits are recorded on the auction, which is
+    /// the only callback account of `finalize_auction_callback`.
+    ///
+    /// Finalization is permissionless, so a creator going offline cannot
+    /// strand bidders' funds. The caller pays the Arcium computation fees
+    /// and is paid the auction's keeper bounty, and a caller other than the
+    /// creator is recorded as the auction's keeper for a share of the
+    /// protocol fee at settlement.
+    ///
+    /// The result must arrive before `finalization_deadline`, set from the
+    /// config's `finalization_timeout`; after that the auction can be
+    /// failed through `abort_finalization`.
+    pub fn request_finalization<'info>(
+        ctx: Context<'_, '_, '_, 'info, RequestFinalization<'info>>,
+        computation_offset: u64,
     ) -> Result<()> {
         let auction = &mut ctx.accounts.auction;
         let clock = Clock::get()?;
 
+        auction.refresh_status(clock.unix_timestamp);
         require!(
             auction.status == AuctionStatus::Active,
             AuctionError::AuctionNotActive
@@ -144,248 +649,5600 @@ pub mod auction {
             AuctionError::AuctionNotEnded
         );
         require!(
-            ctx.accounts.authority.key() == auction.creator,
-            AuctionError::UnauthorizedFinalizer
-        );
-        require!(
-            winning_bid_amount >= auction.min_bid,
-            AuctionError::WinningBidTooLow
+            auction.kind != AuctionKind::CommitReveal,
+            AuctionError::UseFinalizeReveals
         );
 
-        auction.status = AuctionStatus::Finalized;
-        auction.winner = Some(winner_pubkey);
-        auction.winning_bid = Some(winning_bid_amount);
-        auction.mpc_computation_id = Some(mpc_computation_id);
-        auction.finalized_at = Some(clock.unix_timestamp);
+        let bids = load_bids(auction, ctx.remaining_accounts)?;
+        let mut slots: Vec<(Pubkey, Bid)> = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| account.key())
+            .zip(bids)
+            .collect();
+        slots.sort_by_key(|(key, bid)| (bid.timestamp, *key));
+
+        let args = finalize_auction_args(auction, &slots);
+        auction.computation_bids = slots
+            .iter()
+            .map(|(_, bid)| ComputationBid {
+                bidder: bid.bidder,
+                deposit: bid.deposit,
+                allocated_quantity: 0,
+            })
+            .collect();
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![FinalizeAuctionCallback::callback_ix(&[CallbackAccount {
+                pubkey: ctx.accounts.auction.key(),
+                is_writable: true,
+            }])],
+        )?;
+
+        let auction = &mut ctx.accounts.auction;
+        let computation_account = ctx.accounts.computation_account.key();
+        auction.status = AuctionStatus::Finalizing;
+        auction.computation_account = Some(computation_account);
+        auction.mpc_computation_id = Some(computation_account.to_string());
+        auction.finalization_deadline = Some(
+            clock
+                .unix_timestamp
+                .saturating_add(ctx.accounts.config.finalization_timeout),
+        );
+        let keeper_reward = reward_keeper(
+            auction,
+            &ctx.accounts.vault,
+            &ctx.accounts.authority.to_account_info(),
+        )?;
 
         msg!(
-            "Auction finalized - Winner: {}, Amount: {}",
-            winner_pubkey,
-            winning_bid_amount
+            "Finalization queued - Auction: {}, Computation: {}",
+            auction.key(),
+            computation_account
         );
+        emit!(FinalizationRequested {
+            auction: auction.key(),
+            requester: ctx.accounts.authority.key(),
+            keeper_reward,
+            computation_account,
+            bid_count: auction.bid_count,
+            timestamp: clock.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    /// Cancel auction (only if no bids submitted)
-    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
-        let auction = &mut ctx.accounts.auction;
+    /// Open a bid commitment in a commit-reveal auction
+    ///
+    /// Allowed between `end_time` and `reveal_end_time`. The opening must
+    /// hash to the commitment stored by `submit_bid`. Bids that are never
+    /// revealed forfeit their deposit to the creator.
+    ///
+    /// Not subject to the pause or freezes, since `reveal_end_time` keeps
+    /// running and a blocked reveal would forfeit the deposit.
+    pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+        let bid = &mut ctx.accounts.bid;
+        let clock = Clock::get()?;
 
         require!(
-            ctx.accounts.creator.key() == auction.creator,
-            AuctionError::UnauthorizedCancellation
+            auction.kind == AuctionKind::CommitReveal,
+            AuctionError::NotCommitReveal
         );
         require!(
             auction.status == AuctionStatus::Active,
             AuctionError::AuctionNotActive
         );
         require!(
-            auction.bid_count == 0,
-            AuctionError::CannotCancelWithBids
+            clock.unix_timestamp >= auction.end_time,
+            AuctionError::AuctionNotEnded
+        );
+        require!(
+            auction
+                .reveal_end_time
+                .is_some_and(|reveal_end| clock.unix_timestamp < reveal_end),
+            AuctionError::RevealPhaseEnded
+        );
+        require!(
+            bid.auction == auction.key(),
+            AuctionError::BidAuctionMismatch
+        );
+        require!(
+            bid.bidder == ctx.accounts.bidder.key(),
+            AuctionError::UnauthorizedClaim
+        );
+        require!(bid.revealed_amount.is_none(), AuctionError::AlreadyRevealed);
+        require!(
+            bid.encrypted_data[..] == bid_commitment(&auction.key(), &bid.bidder, amount, &salt),
+            AuctionError::InvalidReveal
         );
 
-        auction.status = AuctionStatus::Cancelled;
+        bid.revealed_amount = Some(amount);
+
+        msg!(
+            "Bid revealed - Bidder: {}, Amount: {}",
+            bid.bidder,
+            amount
+        );
+        emit!(BidRevealed {
+            auction: auction.key(),
+            bidder: bid.bidder,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
 
-        msg!("Auction cancelled by creator");
         Ok(())
     }
-}
-
-// ============================================================================
-// Account Structures
-// ============================================================================
 
-#[derive(Accounts)]
-#[instruction(item_name: String)]
-pub struct CreateAuction<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + Auction::INIT_SPACE,
-        seeds = [b"auction", creator.key().as_ref(), item_name.as_bytes()],
-        bump
-    )]
-    pub auction: Account<'info, Auction>,
+    /// Resolve a commit-reveal auction on-chain from the revealed bids
+    ///
+    /// Can be called once the reveal phase is over. Every `Bid` account of
+    /// the auction must be passed in `remaining_accounts`. The highest
+    /// revealed bid that meets `min_bid` and is covered by its deposit wins,
+    /// with ties going to the earliest bid. Deposits of unrevealed bids are
+    /// forfeited to the creator. Like `request_finalization` it can be
+    /// triggered by anyone, who collects the keeper bounty.
+    pub fn finalize_reveals<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeReveals<'info>>,
+    ) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+        auction.refresh_status(clock.unix_timestamp);
+        require!(
+            auction.kind == AuctionKind::CommitReveal,
+            AuctionError::NotCommitReveal
+        );
+        require!(
+            auction.status == AuctionStatus::Active,
+            AuctionError::AuctionNotActive
+        );
+        require!(
+            auction
+                .reveal_end_time
+                .is_some_and(|reveal_end| clock.unix_timestamp >= reveal_end),
+            AuctionError::RevealPhaseNotEnded
+        );
 
-    pub system_program: Program<'info, System>,
-}
+        let bids = load_bids(auction, ctx.remaining_accounts)?;
+        let (best, forfeited) = select_reveal_winner(&bids, auction.min_bid)?;
 
-#[derive(Accounts)]
-pub struct SubmitBid<'info> {
-    #[account(mut)]
-    pub auction: Account<'info, Auction>,
+        if let Some(winner_bid) = best {
+            let amount = winner_bid.revealed_amount.unwrap_or_default();
+            auction.winner = Some(winner_bid.bidder);
+            auction.winning_bid = Some(amount);
+            auction.clearing_price = Some(auction.clearing_price(amount, None));
 
-    #[account(
-        init,
-        payer = bidder,
-        space = 8 + Bid::INIT_SPACE,
-        seeds = [
-            b"bid",
-            auction.key().as_ref(),
-            bidder.key().as_ref(),
-            &auction.bid_count.to_le_bytes()
-        ],
-        bump
-    )]
-    pub bid: Account<'info, Bid>,
+            msg!(
+                "Auction finalized - Winner: {}, Amount: {}",
+                winner_bid.bidder,
+                amount
+            );
+        } else {
+            msg!("Auction finalized - No revealed bid met the minimum");
+        }
 
-    #[account(mut)]
-    pub bidder: Signer<'info>,
+        auction.forfeited_deposits = forfeited;
+        auction.status = AuctionStatus::Finalized;
+        auction.finalized_at = Some(clock.unix_timestamp);
+        reward_keeper(
+            auction,
+            &ctx.accounts.vault,
+            &ctx.accounts.authority.to_account_info(),
+        )?;
+        emit_finalized(auction);
 
-    pub system_program: Program<'info, System>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct FinalizeAuction<'info> {
-    #[account(mut)]
-    pub auction: Account<'info, Auction>,
+    /// Accept the MPC result for a queued finalization
+    ///
+    /// Invoked by the Arcium program when the `finalize_auction` computation
+    /// completes. `#[arcium_callback]` checks through the instructions sysvar
+    /// that the call comes from Arcium's computation finalization, so the
+    /// result cannot be forged by the creator or any other party. A failed
+    /// computation is rejected and leaves the auction to `abort_finalization`.
+    ///
+    /// The revealed `Outcome` refers to bids by slot, and is decoded against
+    /// the auction's `computation_bids` (see `ComputationResult::from_outcome`).
+    /// The clearing price the winner pays is derived from the result using
+    /// the auction's pricing rule (see `Auction::clearing_price`).
+    ///
+    /// If the auction has an encrypted reserve that no bid met, the auction
+    /// resolves to `ReserveNotMet` and no winner or amount is recorded. Such a
+    /// result must not carry a winner, amounts or allocations.
+    ///
+    /// Uniform-price auctions receive a list of allocations instead of a
+    /// single winner, recorded on the auction's `computation_bids`.
+    ///
+    /// In partially funded auctions the runner-up is recorded, and a winner
+    /// whose price exceeds their collateral moves the auction to
+    /// `AwaitingPayment` until `payment_deadline`.
+    ///
+    /// Not subject to the pause or freezes, since a result held back past
+    /// `finalization_deadline` could otherwise be aborted.
+    #[arcium_callback(encrypted_ix = "finalize_auction")]
+    pub fn finalize_auction_callback(
+        ctx: Context<FinalizeAuctionCallback>,
+        output: ComputationOutputs<FinalizeAuctionOutput>,
+    ) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
 
-    pub authority: Signer<'info>,
-}
+        require!(
+            auction.status == AuctionStatus::Finalizing,
+            AuctionError::AuctionNotFinalizing
+        );
+        let outcome = match output {
+            ComputationOutputs::Success(FinalizeAuctionOutput { field_0 }) => field_0,
+            ComputationOutputs::Failure => return err!(AuctionError::ComputationFailed),
+        };
+        let result = ComputationResult::from_outcome(auction, &outcome)?;
+        if result.rejected_bids > 0 {
+            msg!("Bids failing authentication: {}", result.rejected_bids);
+        }
 
-#[derive(Accounts)]
-pub struct CancelAuction<'info> {
-    #[account(mut)]
-    pub auction: Account<'info, Auction>,
+        if auction.encrypted_reserve.is_some() && !result.reserve_met {
+            require!(
+                result.winner.is_none()
+                    && result.winning_bid == 0
+                    && result.second_bid.is_none()
+                    && result.runner_up.is_none()
+                    && result.allocations.is_empty(),
+                AuctionError::InvalidComputationResult
+            );
+            auction.status = AuctionStatus::ReserveNotMet;
+            auction.finalized_at = Some(clock.unix_timestamp);
 
-    pub creator: Signer<'info>,
-}
+            msg!("Auction resolved - Reserve not met");
+            emit_finalized(auction);
+            return Ok(());
+        }
 
-// ============================================================================
-// Data Structures
-// ============================================================================
+        if auction.kind == AuctionKind::UniformPrice {
+            record_allocations(auction, &result)?;
+            auction.status = AuctionStatus::Finalized;
+            auction.finalized_at = Some(clock.unix_timestamp);
 
-#[account]
-#[derive(InitSpace)]
-pub struct Auction {
-    /// Creator's public key
-    pub creator: Pubkey,
+            msg!(
+                "Auction finalized - Units sold: {}, Price: {}",
+                auction.units_sold,
+                result.winning_bid
+            );
+            emit_finalized(auction);
+            return Ok(());
+        }
 
-    /// Item being auctioned
-    #[max_len(64)]
-    pub item_name: String,
+        match result.winner {
+            Some(winner) => {
+                let deposit = auction
+                    .computation_bid(&winner)
+                    .ok_or(AuctionError::NotWinningBid)?
+                    .deposit;
+                require!(
+                    result.winning_bid >= auction.min_bid,
+                    AuctionError::WinningBidTooLow
+                );
+                require!(
+                    auction.payment_window > 0 || result.winning_bid <= deposit,
+                    AuctionError::InsufficientDeposit
+                );
 
-    /// Description of the item
-    #[max_len(256)]
-    pub description: String,
+                let clearing_price = auction.clearing_price(result.winning_bid, result.second_bid);
+                auction.winner = Some(winner);
+                auction.winning_bid = Some(result.winning_bid);
+                auction.clearing_price = Some(clearing_price);
 
-    /// Minimum bid amount in lamports
-    pub min_bid: u64,
+                if auction.payment_window > 0 {
+                    if let Some((runner_up, second_bid)) = result.runner_up.zip(result.second_bid) {
+                        require!(runner_up != winner, AuctionError::InvalidRunnerUp);
+                        if second_bid >= auction.min_bid {
+                            auction.runner_up = Some(runner_up);
+                            auction.runner_up_bid = Some(second_bid);
+                        }
+                    }
+                    if clearing_price > deposit {
+                        auction.payment_deadline =
+                            Some(clock.unix_timestamp.saturating_add(auction.payment_window));
+                    }
+                }
 
-    /// Auction end timestamp
-    pub end_time: i64,
+                msg!(
+                    "Auction finalized - Winner: {}, Highest bid: {}, Price: {}",
+                    winner,
+                    result.winning_bid,
+                    clearing_price
+                );
+            }
+            None => {
+                msg!("Auction finalized - No bid met the minimum");
+            }
+        }
 
-    /// Auction creation timestamp
-    pub created_at: i64,
+        auction.status = if auction.payment_deadline.is_some() {
+            AuctionStatus::AwaitingPayment
+        } else {
+            AuctionStatus::Finalized
+        };
+        auction.finalized_at = Some(clock.unix_timestamp);
+        emit_finalized(auction);
 
-    /// Current status
-    pub status: AuctionStatus,
+        Ok(())
+    }
 
-    /// Total number of bids
-    pub bid_count: u64,
+    /// Fail an auction whose MPC result did not arrive in time
+    ///
+    /// Callable by anyone once `finalization_deadline` has passed. The
+    /// auction moves to `Failed`, which unlocks refunds for every bidder and
+    /// returns the escrowed asset to the creator through `reclaim_asset`. A
+    /// late callback is rejected. The computation account and ID stay on
+    /// the auction for diagnosis.
+    ///
+    /// An auction nobody finalized is treated the same way: once it is
+    /// still `Active` the config's `finalization_timeout` after bidding
+    /// (or the reveal phase) closed, it can be failed too.
+    ///
+    /// Aborting a queued computation is not subject to the pause, since it
+    /// only unlocks funds. Failing an `Active` auction is blocked while the
+    /// protocol is paused or the auction frozen, as finalization cannot be
+    /// requested then either.
+    pub fn abort_finalization(ctx: Context<AbortFinalization>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
 
-    /// Arcium MXE cluster public key (for client-side encryption)
-    pub arcium_mxe_pubkey: [u8; 32],
+        auction.refresh_status(clock.unix_timestamp);
+        if auction.status == AuctionStatus::Active {
+            let config = &ctx.accounts.config;
+            require!(!config.paused, AuctionError::ProtocolPaused);
+            require!(!auction.frozen, AuctionError::AuctionFrozen);
 
-    /// Winner's public key (revealed after finalization)
-    pub winner: Option<Pubkey>,
+            let closed_at = auction.reveal_end_time.unwrap_or(auction.end_time);
+            auction.finalization_deadline =
+                Some(closed_at.saturating_add(config.finalization_timeout));
+        } else {
+            require!(
+                auction.status == AuctionStatus::Finalizing,
+                AuctionError::AuctionNotFinalizing
+            );
+        }
+        require!(
+            auction
+                .finalization_deadline
+                .is_some_and(|deadline| clock.unix_timestamp >= deadline),
+            AuctionError::FinalizationDeadlineNotReached
+        );
 
-    /// Winning bid amount (revealed after finalization)
-    pub winning_bid: Option<u64>,
+        auction.status = AuctionStatus::Failed;
+        auction.finalized_at = Some(clock.unix_timestamp);
 
-    /// MPC computation ID from Arcium
-    #[max_len(64)]
-    pub mpc_computation_id: Option<String>,
+        msg!(
+            "Finalization aborted - Auction: {}, Computation: {:?}",
+            auction.key(),
+            auction.mpc_computation_id
+        );
+        emit!(FinalizationAborted {
+            auction: auction.key(),
+            caller: ctx.accounts.caller.key(),
+            computation_account: auction.computation_account,
+            mpc_computation_id: auction.mpc_computation_id.clone(),
+            finalization_deadline: auction.finalization_deadline,
+            timestamp: clock.unix_timestamp,
+        });
 
-    /// Finalization timestamp
-    pub finalized_at: Option<i64>,
+        Ok(())
+    }
 
-    /// PDA bump
-    pub bump: u8,
-}
+    /// Pay the balance of a partially funded winning bid
+    ///
+    /// Tops the winner's deposit up to the clearing price before
+    /// `payment_deadline`, after which the auction is `Finalized` and
+    /// settles through `claim_proceeds`.
+    ///
+    /// Not subject to the pause or freezes, since `payment_deadline` keeps
+    /// running and `claim_default` would otherwise slash an honest winner.
+    pub fn pay_balance(ctx: Context<PayBalance>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let winner_bid = &mut ctx.accounts.winner_bid;
+        let clock = Clock::get()?;
 
-#[account]
-#[derive(InitSpace)]
-pub struct Bid {
-    /// Reference to auction
-    pub auction: Pubkey,
+        require!(
+            auction.status == AuctionStatus::AwaitingPayment,
+            AuctionError::NotAwaitingPayment
+        );
+        require!(
+            auction
+                .payment_deadline
+                .is_some_and(|deadline| clock.unix_timestamp < deadline),
+            AuctionError::PaymentDeadlinePassed
+        );
+        require!(
+            auction.winner == Some(winner_bid.bidder),
+            AuctionError::NotWinningBid
+        );
 
-    /// Bidder's wallet public key
-    pub bidder: Pubkey,
+        let price = auction.clearing_price.ok_or(AuctionError::NotWinningBid)?;
+        let balance = price.saturating_sub(winner_bid.deposit);
 
-    /// Encrypted bid data (output of Rescue cipher)
-    /// Contains the encrypted bid amount
-    pub encrypted_data: Vec<u8>,
+        Escrow::new(
+            auction,
+            &ctx.accounts.vault,
+            ctx.accounts.quote_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?
+        .deposit(
+            &ctx.accounts.winner,
+            ctx.accounts.winner_token_account.as_ref(),
+            &ctx.accounts.system_program,
+            balance,
+        )?;
+        winner_bid.deposit = winner_bid
+            .deposit
+            .checked_add(balance)
+            .ok_or(AuctionError::InsufficientEscrow)?;
+        auction.status = AuctionStatus::Finalized;
 
-    /// Ephemeral x25519 public key used for encryption
-    /// Used in key exchange with Arcium MXE public key
-    pub x25519_pubkey: [u8; 32],
+        msg!(
+            "Balance paid - Winner: {}, Amount: {}",
+            winner_bid.bidder,
+            balance
+        );
+        emit!(BalancePaid {
+            auction: auction.key(),
+            winner: winner_bid.bidder,
+            amount: balance,
+            price,
+            timestamp: clock.unix_timestamp,
+        });
 
-    /// Nonce used for Rescue cipher encryption
-    pub nonce: [u8; 16],
+        Ok(())
+    }
 
-    /// Submission timestamp
-    pub timestamp: i64,
+    /// Resolve a winner's failure to pay by `payment_deadline`
+    ///
+    /// Callable by anyone once the deadline has passed. The defaulting
+    /// winner's collateral is slashed: the protocol fee share goes to the
+    /// fee recipient and the rest to the creator. Under the
+    /// `OfferRunnerUp` policy the item then passes to the runner-up at
+    /// their own bid, whose `Bid` account must be passed; they get a new
+    /// payment window if their collateral does not cover it. Otherwise,
+    /// or when there is no runner-up left, the auction is `Defaulted`,
+    /// which unlocks refunds and returns the asset to the creator.
+    pub fn claim_default(ctx: Context<ClaimDefault>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let winner_bid = &mut ctx.accounts.winner_bid;
+        let clock = Clock::get()?;
 
-    /// PDA bump
-    pub bump: u8,
+        require!(
+            auction.status == AuctionStatus::AwaitingPayment,
+            AuctionError::NotAwaitingPayment
+        );
+        require!(
+            auction
+                .payment_deadline
+                .is_some_and(|deadline| clock.unix_timestamp >= deadline),
+            AuctionError::PaymentDeadlineNotReached
+        );
+        require!(
+            winner_bid.auction == auction.key() && auction.winner == Some(winner_bid.bidder),
+            AuctionError::NotWinningBid
+        );
+        require!(
+            ctx.accounts.creator.key() == auction.creator,
+            AuctionError::UnauthorizedClaim
+        );
+        require!(
+            ctx.accounts.fee_recipient.key() == ctx.accounts.config.fee_recipient,
+            AuctionError::InvalidFeeRecipient
+        );
+
+        // Slash the defaulting winner's collateral
+        let slashed = winner_bid.deposit;
+        let (creator_share, protocol_share) = auction.slash_split(slashed);
+        let escrow = Escrow::new(
+            auction,
+            &ctx.accounts.vault,
+            ctx.accounts.quote_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?;
+        escrow.release(
+            &ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_token_account.as_ref(),
+            creator_share,
+        )?;
+        escrow.release(
+            &ctx.accounts.fee_recipient.to_account_info(),
+            ctx.accounts.fee_recipient_token_account.as_ref(),
+            protocol_share,
+        )?;
+        winner_bid.settled = true;
+        let defaulted_bidder = winner_bid.bidder;
+
+        let runner_up_bid = match &ctx.accounts.runner_up_bid {
+            Some(bid) => {
+                require!(bid.auction == auction.key(), AuctionError::InvalidRunnerUp);
+                Some((bid.bidder, bid.deposit))
+            }
+            None => None,
+        };
+        match auction.resolve_default(runner_up_bid, clock.unix_timestamp)? {
+            Some(runner_up) => msg!(
+                "Winner defaulted - Item offered to runner-up: {}, Price: {}",
+                runner_up,
+                auction.clearing_price.unwrap_or_default()
+            ),
+            None => msg!("Winner defaulted - Auction failed"),
+        }
+
+        emit!(PaymentDefaulted {
+            auction: auction.key(),
+            defaulted_bidder,
+            slashed,
+            protocol_share,
+            next_winner: auction.winner,
+            status: auction.status.clone(),
+            payment_deadline: auction.payment_deadline,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Edit auction metadata while the auction is still upcoming
+    pub fn update_auction(ctx: Context<UpdateAuction>, params: UpdateAuctionParams) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.creator.key() == auction.creator,
+            AuctionError::UnauthorizedUpdate
+        );
+        auction.refresh_status(clock.unix_timestamp);
+        require!(
+            auction.status == AuctionStatus::Upcoming,
+            AuctionError::AuctionAlreadyStarted
+        );
+
+        let config = &ctx.accounts.config;
+        if let Some(item_name) = params.item_name {
+            require!(
+                item_name.len() <= config.max_item_name_len as usize,
+                AuctionError::ItemNameTooLong
+            );
+            auction.item_name = item_name;
+        }
+        if let Some(description) = params.description {
+            require!(
+                description.len() <= config.max_description_len as usize,
+                AuctionError::DescriptionTooLong
+            );
+            auction.description = description;
+        }
+
+        msg!("Auction metadata updated: {}", auction.key());
+        emit!(AuctionUpdated {
+            auction: auction.key(),
+            creator: auction.creator,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Cancel auction (only if no bids submitted)
+    ///
+    /// Upcoming auctions can always be cancelled. An escrowed asset and the
+    /// keeper bounty are returned to the creator.
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+
+        require!(
+            ctx.accounts.creator.key() == auction.creator,
+            AuctionError::UnauthorizedCancellation
+        );
+        require!(
+            auction.status == AuctionStatus::Active
+                || auction.status == AuctionStatus::Upcoming,
+            AuctionError::AuctionNotActive
+        );
+        require!(
+            auction.bid_count == 0,
+            AuctionError::CannotCancelWithBids
+        );
+
+        release_asset(
+            auction,
+            ctx.accounts.asset_vault.as_ref(),
+            &ctx.accounts.creator.key(),
+            ctx.accounts.creator_asset_account.as_ref(),
+            &ctx.accounts.token_program,
+            auction.asset_amount,
+        )?;
+        auction.asset_released = true;
+        auction.status = AuctionStatus::Cancelled;
+        release_from_vault(
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+            auction.keeper_bounty,
+        )?;
+
+        msg!("Auction cancelled by creator");
+        emit!(AuctionCancelled {
+            auction: auction.key(),
+            creator: auction.creator,
+            asset_returned: auction.asset_mint.is_some(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Refund a losing bidder's deposit from escrow
+    ///
+    /// Available once the auction is resolved. The winner's deposit is
+    /// released through `claim_proceeds` instead. Anyone can trigger the
+    /// refund; the deposit always goes to the bidder.
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+        let bid = &mut ctx.accounts.bid;
+
+        require!(auction.is_resolved(), AuctionError::AuctionNotSettled);
+        require!(
+            bid.auction == auction.key(),
+            AuctionError::BidAuctionMismatch
+        );
+        require!(
+            auction.winner != Some(bid.bidder) && auction.allocated_quantity(&bid.bidder) == 0,
+            AuctionError::WinnerCannotRefund
+        );
+        require!(
+            auction.kind != AuctionKind::CommitReveal
+                || auction.status != AuctionStatus::Finalized
+                || bid.revealed_amount.is_some(),
+            AuctionError::DepositForfeited
+        );
+        require!(!bid.settled, AuctionError::AlreadySettled);
+
+        Escrow::new(
+            auction,
+            &ctx.accounts.vault,
+            ctx.accounts.quote_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?
+        .release(
+            &ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.bidder_token_account.as_ref(),
+            bid.deposit,
+        )?;
+        bid.settled = true;
+
+        msg!(
+            "Deposit refunded - Bidder: {}, Amount: {}",
+            bid.bidder,
+            bid.deposit
+        );
+        emit!(DepositRefunded {
+            auction: auction.key(),
+            bidder: bid.bidder,
+            amount: bid.deposit,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pay the creator the clearing price from escrow
+    ///
+    /// The clearing price is taken from the winner's deposit and any excess
+    /// deposit is returned to the winner in the same instruction. For
+    /// asset-backed auctions the escrowed asset is released to the winner
+    /// atomically with the payment. Either the creator or the winner can
+    /// trigger settlement.
+    ///
+    /// The auction's protocol fee is taken out of the payment and sent to
+    /// the configured fee recipient, less the keeper's share when a keeper
+    /// finalized the auction. The rest is paid to the auction's
+    /// revenue split, whose recipient accounts are passed in
+    /// `remaining_accounts` (see `pay_revenue_shares`).
+    pub fn claim_proceeds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimProceeds<'info>>,
+    ) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let winner_bid = &mut ctx.accounts.winner_bid;
+
+        require!(
+            ctx.accounts.creator.key() == auction.creator,
+            AuctionError::UnauthorizedClaim
+        );
+        require!(
+            ctx.accounts.authority.key() == auction.creator
+                || ctx.accounts.authority.key() == winner_bid.bidder,
+            AuctionError::UnauthorizedClaim
+        );
+        require!(
+            auction.status == AuctionStatus::Finalized,
+            AuctionError::AuctionNotSettled
+        );
+        require!(
+            auction.kind != AuctionKind::UniformPrice,
+            AuctionError::UseClaimAllocation
+        );
+        require!(!auction.proceeds_claimed, AuctionError::AlreadySettled);
+        require!(
+            winner_bid.auction == auction.key(),
+            AuctionError::BidAuctionMismatch
+        );
+        require!(
+            auction.winner == Some(winner_bid.bidder)
+                && ctx.accounts.winner.key() == winner_bid.bidder,
+            AuctionError::NotWinningBid
+        );
+        require!(
+            ctx.accounts.fee_recipient.key() == ctx.accounts.config.fee_recipient,
+            AuctionError::InvalidFeeRecipient
+        );
+
+        let price = auction.clearing_price.ok_or(AuctionError::NotWinningBid)?;
+        let excess = winner_bid
+            .deposit
+            .checked_sub(price)
+            .ok_or(AuctionError::InsufficientDeposit)?;
+        let fee = auction.protocol_fee(price);
+        let keeper_fee = auction.keeper_fee(fee);
+
+        let escrow = Escrow::new(
+            auction,
+            &ctx.accounts.vault,
+            ctx.accounts.quote_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?;
+        pay_revenue_shares(
+            &escrow,
+            &ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_token_account.as_ref(),
+            ctx.remaining_accounts,
+            price - fee,
+        )?;
+        escrow.release(
+            &ctx.accounts.fee_recipient.to_account_info(),
+            ctx.accounts.fee_recipient_token_account.as_ref(),
+            fee - keeper_fee,
+        )?;
+        if keeper_fee > 0 {
+            let keeper = ctx
+                .accounts
+                .keeper
+                .as_ref()
+                .ok_or(AuctionError::InvalidKeeper)?;
+            require!(
+                auction.keeper == Some(keeper.key()),
+                AuctionError::InvalidKeeper
+            );
+            escrow.release(
+                &keeper.to_account_info(),
+                ctx.accounts.keeper_token_account.as_ref(),
+                keeper_fee,
+            )?;
+        }
+        escrow.release(
+            &ctx.accounts.winner.to_account_info(),
+            ctx.accounts.winner_token_account.as_ref(),
+            excess,
+        )?;
+        release_asset(
+            auction,
+            ctx.accounts.asset_vault.as_ref(),
+            &winner_bid.bidder,
+            ctx.accounts.winner_asset_account.as_ref(),
+            &ctx.accounts.token_program,
+            auction.asset_amount,
+        )?;
+
+        auction.proceeds_claimed = true;
+        auction.asset_released = true;
+        winner_bid.settled = true;
+
+        msg!(
+            "Proceeds claimed - Creator: {}, Amount: {}, Fee: {}, Winner refund: {}",
+            auction.creator,
+            price - fee,
+            fee,
+            excess
+        );
+        emit!(ProceedsClaimed {
+            auction: auction.key(),
+            creator: auction.creator,
+            winner: winner_bid.bidder,
+            price,
+            protocol_fee: fee,
+            keeper_fee,
+            winner_refund: excess,
+            asset_released: auction.asset_mint.is_some(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Return the escrowed asset to the creator when there is no winner
+    ///
+    /// Used when the auction finalized without any bid meeting `min_bid`,
+    /// when the encrypted reserve was not met, or when finalization failed. For uniform-price
+    /// auctions this returns the units that were not allocated.
+    pub fn reclaim_asset(ctx: Context<ReclaimAsset>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+
+        require!(
+            ctx.accounts.creator.key() == auction.creator,
+            AuctionError::UnauthorizedClaim
+        );
+        require!(auction.is_unsold(), AuctionError::AuctionNotSettled);
+        require!(!auction.asset_released, AuctionError::AlreadySettled);
+
+        release_asset(
+            auction,
+            ctx.accounts.asset_vault.as_ref(),
+            &ctx.accounts.creator.key(),
+            ctx.accounts.creator_asset_account.as_ref(),
+            &ctx.accounts.token_program,
+            auction.unsold_asset_amount(),
+        )?;
+        auction.asset_released = true;
+
+        msg!("Asset returned to creator: {}", auction.creator);
+        emit!(AssetReclaimed {
+            auction: auction.key(),
+            creator: auction.creator,
+            amount: auction.unsold_asset_amount(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Pay the creator the deposits forfeited by unrevealed bids
+    ///
+    /// Commit-reveal auctions only; the total is fixed by `finalize_reveals`.
+    pub fn claim_forfeits(ctx: Context<ClaimForfeits>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+
+        require!(
+            ctx.accounts.creator.key() == auction.creator,
+            AuctionError::UnauthorizedClaim
+        );
+        require!(
+            auction.kind == AuctionKind::CommitReveal
+                && auction.status == AuctionStatus::Finalized,
+            AuctionError::AuctionNotSettled
+        );
+        require!(!auction.forfeits_claimed, AuctionError::AlreadySettled);
+
+        Escrow::new(
+            auction,
+            &ctx.accounts.vault,
+            ctx.accounts.quote_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?
+        .release(
+            &ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_token_account.as_ref(),
+            auction.forfeited_deposits,
+        )?;
+
+        let auction = &mut ctx.accounts.auction;
+        auction.forfeits_claimed = true;
+
+        msg!(
+            "Forfeited deposits claimed - Creator: {}, Amount: {}",
+            auction.creator,
+            auction.forfeited_deposits
+        );
+        emit!(ForfeitsClaimed {
+            auction: auction.key(),
+            creator: auction.creator,
+            amount: auction.forfeited_deposits,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim and settle a winning allocation in a uniform-price auction
+    ///
+    /// Creates the winner's `Allocation` PDA, pays the creator the clearing
+    /// price for every allocated unit less the protocol fee, refunds the rest
+    /// of the deposit and releases the allocated asset units to the winner.
+    /// Revenue split recipients are passed as in `claim_proceeds`.
+    ///
+    /// Like `claim_proceeds` it can be triggered by the creator or the
+    /// winner, with `payer` funding the `Allocation` account.
+    pub fn claim_allocation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimAllocation<'info>>,
+    ) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+        let bid = &mut ctx.accounts.bid;
+        let clock = Clock::get()?;
+
+        require!(
+            auction.kind == AuctionKind::UniformPrice
+                && auction.status == AuctionStatus::Finalized,
+            AuctionError::AuctionNotSettled
+        );
+        require!(
+            bid.auction == auction.key(),
+            AuctionError::BidAuctionMismatch
+        );
+        require!(
+            bid.bidder == ctx.accounts.bidder.key(),
+            AuctionError::UnauthorizedClaim
+        );
+        require!(
+            ctx.accounts.creator.key() == auction.creator,
+            AuctionError::UnauthorizedClaim
+        );
+        require!(
+            ctx.accounts.authority.key() == auction.creator
+                || ctx.accounts.authority.key() == bid.bidder,
+            AuctionError::UnauthorizedClaim
+        );
+        let quantity = auction.allocated_quantity(&bid.bidder);
+        require!(quantity > 0, AuctionError::NotWinningBid);
+        require!(!bid.settled, AuctionError::AlreadySettled);
+        require!(
+            ctx.accounts.fee_recipient.key() == ctx.accounts.config.fee_recipient,
+            AuctionError::InvalidFeeRecipient
+        );
+
+        let price = auction.clearing_price.ok_or(AuctionError::NotWinningBid)?;
+        let payment = price
+            .checked_mul(quantity)
+            .ok_or(AuctionError::InsufficientDeposit)?;
+        let excess = bid
+            .deposit
+            .checked_sub(payment)
+            .ok_or(AuctionError::InsufficientDeposit)?;
+        let fee = auction.protocol_fee(payment);
+        let keeper_fee = auction.keeper_fee(fee);
+
+        let escrow = Escrow::new(
+            auction,
+            &ctx.accounts.vault,
+            ctx.accounts.quote_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?;
+        pay_revenue_shares(
+            &escrow,
+            &ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_token_account.as_ref(),
+            ctx.remaining_accounts,
+            payment - fee,
+        )?;
+        escrow.release(
+            &ctx.accounts.fee_recipient.to_account_info(),
+            ctx.accounts.fee_recipient_token_account.as_ref(),
+            fee - keeper_fee,
+        )?;
+        if keeper_fee > 0 {
+            let keeper = ctx
+                .accounts
+                .keeper
+                .as_ref()
+                .ok_or(AuctionError::InvalidKeeper)?;
+            require!(
+                auction.keeper == Some(keeper.key()),
+                AuctionError::InvalidKeeper
+            );
+            escrow.release(
+                &keeper.to_account_info(),
+                ctx.accounts.keeper_token_account.as_ref(),
+                keeper_fee,
+            )?;
+        }
+        escrow.release(
+            &ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.bidder_token_account.as_ref(),
+            excess,
+        )?;
+        release_asset(
+            auction,
+            ctx.accounts.asset_vault.as_ref(),
+            &bid.bidder,
+            ctx.accounts.bidder_asset_account.as_ref(),
+            &ctx.accounts.token_program,
+            auction.units_per_item() * quantity,
+        )?;
+
+        let allocation = &mut ctx.accounts.allocation;
+        allocation.auction = auction.key();
+        allocation.bidder = bid.bidder;
+        allocation.bid = bid.key();
+        allocation.quantity = quantity;
+        allocation.price = price;
+        allocation.claimed_at = clock.unix_timestamp;
+        allocation.payer = ctx.accounts.payer.key();
+        allocation.bump = ctx.bumps.allocation;
+        bid.settled = true;
+
+        msg!(
+            "Allocation claimed - Bidder: {}, Quantity: {}, Payment: {}",
+            bid.bidder,
+            allocation.quantity,
+            payment
+        );
+        emit!(AllocationClaimed {
+            auction: auction.key(),
+            bidder: bid.bidder,
+            quantity: allocation.quantity,
+            price,
+            payment,
+            protocol_fee: fee,
+            keeper_fee,
+            refund: excess,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close a claimed allocation and return its rent to whoever paid for it
+    ///
+    /// An allocation only records a settled claim, which `AllocationClaimed`
+    /// also logs, so the bidder or the payer can close it at any time. The
+    /// bid stays settled, so the allocation cannot be claimed again.
+    pub fn close_allocation(ctx: Context<CloseAllocation>) -> Result<()> {
+        let allocation = &ctx.accounts.allocation;
+        let authority = ctx.accounts.authority.key();
+
+        require!(
+            authority == allocation.bidder || authority == allocation.payer,
+            AuctionError::UnauthorizedClaim
+        );
+
+        msg!(
+            "Allocation closed - Bidder: {}, Auction: {}",
+            allocation.bidder,
+            allocation.auction
+        );
+        Ok(())
+    }
+
+    /// Close a settled bid and return its rent to the bidder
+    ///
+    /// Requires the auction to be resolved and the bid's deposit to have
+    /// been refunded or settled (or forfeited in a commit-reveal auction).
+    /// Anyone can close it, so an absent bidder cannot hold up
+    /// `close_auction`; the rent always goes to the bidder.
+    pub fn close_bid(ctx: Context<CloseBid>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let bid = &ctx.accounts.bid;
+
+        require!(auction.is_resolved(), AuctionError::AuctionNotSettled);
+        let forfeited = auction.kind == AuctionKind::CommitReveal
+            && auction.status == AuctionStatus::Finalized
+            && bid.revealed_amount.is_none();
+        require!(bid.settled || forfeited, AuctionError::SettlementPending);
+
+        auction.closed_bid_count = auction.closed_bid_count.checked_add(1).unwrap();
+
+        msg!(
+            "Bid closed - Bidder: {}, Auction: {}",
+            bid.bidder,
+            auction.key()
+        );
+        emit!(BidClosed {
+            auction: auction.key(),
+            bidder: bid.bidder,
+            closed_bid_count: auction.closed_bid_count,
+            bid_count: auction.bid_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close a nonce registry and return its rent to the bidder
+    ///
+    /// Allowed once the auction is resolved, or after it has been closed.
+    pub fn close_nonce_registry(ctx: Context<CloseNonceRegistry>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+        let registry = &ctx.accounts.nonce_registry;
+
+        require!(
+            registry.bidder == ctx.accounts.bidder.key(),
+            AuctionError::UnauthorizedClaim
+        );
+        if !auction.data_is_empty() {
+            require!(auction.owner == &crate::ID, AuctionError::BidAuctionMismatch);
+            let auction = Auction::try_deserialize(&mut &auction.try_borrow_data()?[..])?;
+            require!(auction.is_resolved(), AuctionError::AuctionNotSettled);
+        }
+
+        msg!(
+            "Nonce registry closed - Bidder: {}, Auction: {}",
+            registry.bidder,
+            registry.auction
+        );
+        Ok(())
+    }
+
+    /// Close a resolved auction and return all rent to the creator
+    ///
+    /// Requires settlement to be complete and every bid to have been closed
+    /// or withdrawn. The vault and any token vaults are closed as well, with
+    /// tokens left in a token vault swept to the creator's token account.
+    pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+
+        require!(
+            ctx.accounts.creator.key() == auction.creator,
+            AuctionError::UnauthorizedClaim
+        );
+        require!(auction.is_resolved(), AuctionError::AuctionNotSettled);
+        require!(
+            auction.is_settlement_complete(),
+            AuctionError::SettlementPending
+        );
+
+        if let Some(quote_mint) = auction.quote_mint {
+            let quote_vault = ctx
+                .accounts
+                .quote_vault
+                .as_ref()
+                .ok_or(AuctionError::MissingTokenAccount)?;
+            require!(
+                quote_vault.key() == get_associated_token_address(&auction.key(), &quote_mint),
+                AuctionError::InvalidQuoteVault
+            );
+            close_auction_token_account(
+                auction,
+                quote_vault,
+                &ctx.accounts.creator,
+                ctx.accounts.creator_token_account.as_ref(),
+                &ctx.accounts.token_program,
+            )?;
+        }
+        if let Some(asset_mint) = auction.asset_mint {
+            let asset_vault = ctx
+                .accounts
+                .asset_vault
+                .as_ref()
+                .ok_or(AuctionError::MissingTokenAccount)?;
+            require!(
+                asset_vault.key() == get_associated_token_address(&auction.key(), &asset_mint),
+                AuctionError::InvalidAssetVault
+            );
+            close_auction_token_account(
+                auction,
+                asset_vault,
+                &ctx.accounts.creator,
+                ctx.accounts.creator_asset_account.as_ref(),
+                &ctx.accounts.token_program,
+            )?;
+        }
+
+        msg!("Auction closed: {}", auction.key());
+        emit!(AuctionClosed {
+            auction: auction.key(),
+            creator: auction.creator,
+            bid_count: auction.bid_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Create the singleton protocol config
+    ///
+    /// Only the program's upgrade authority can initialize it, so the
+    /// config cannot be claimed by whoever calls first after deployment.
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.admin = ctx.accounts.admin.key();
+        config.pause_authority = params.pause_authority;
+        config.paused = false;
+        config.fee_bps = params.fee_bps;
+        config.fee_recipient = params.fee_recipient;
+        config.keeper_fee_bps = params.keeper_fee_bps;
+        config.max_item_name_len = params.max_item_name_len;
+        config.max_description_len = params.max_description_len;
+        config.min_duration = params.min_duration;
+        config.max_duration = params.max_duration;
+        config.finalization_timeout = params.finalization_timeout;
+        config.bump = ctx.bumps.config;
+        config.validate()?;
+
+        msg!("Protocol config initialized - Admin: {}", config.admin);
+        emit_config_updated(config)
+    }
+
+    /// Update protocol settings, or hand the config to a new admin
+    ///
+    /// Fee changes only apply to auctions created afterwards.
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            ctx.accounts.admin.key() == config.admin,
+            AuctionError::UnauthorizedAdmin
+        );
+
+        if let Some(admin) = params.admin {
+            config.admin = admin;
+        }
+        if let Some(pause_authority) = params.pause_authority {
+            config.pause_authority = pause_authority;
+        }
+        if let Some(fee_bps) = params.fee_bps {
+            config.fee_bps = fee_bps;
+        }
+        if let Some(fee_recipient) = params.fee_recipient {
+            config.fee_recipient = fee_recipient;
+        }
+        if let Some(keeper_fee_bps) = params.keeper_fee_bps {
+            config.keeper_fee_bps = keeper_fee_bps;
+        }
+        if let Some(max_item_name_len) = params.max_item_name_len {
+            config.max_item_name_len = max_item_name_len;
+        }
+        if let Some(max_description_len) = params.max_description_len {
+            config.max_description_len = max_description_len;
+        }
+        if let Some(min_duration) = params.min_duration {
+            config.min_duration = min_duration;
+        }
+        if let Some(max_duration) = params.max_duration {
+            config.max_duration = max_duration;
+        }
+        if let Some(finalization_timeout) = params.finalization_timeout {
+            config.finalization_timeout = finalization_timeout;
+        }
+        config.validate()?;
+
+        msg!("Protocol config updated - Admin: {}", config.admin);
+        emit_config_updated(config)
+    }
+
+    /// Pause or resume the whole protocol
+    ///
+    /// While paused every mutating instruction fails with `ProtocolPaused`,
+    /// except `claim_refund`, `withdraw_bid`, `reclaim_asset` and
+    /// `abort_finalization`, so users can always recover their funds, and
+    /// `reveal_bid`, `finalize_auction_callback` and `pay_balance`, whose
+    /// deadlines keep running.
+    /// `reason` is an off-chain incident code recorded in the emitted event.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool, reason: u16) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            config.can_pause(&ctx.accounts.authority.key()),
+            AuctionError::UnauthorizedPauser
+        );
+        config.paused = paused;
+
+        msg!("Protocol paused: {}, Reason: {}", paused, reason);
+        emit!(ProtocolPauseChanged {
+            authority: ctx.accounts.authority.key(),
+            paused,
+            reason,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Freeze or unfreeze a single auction
+    ///
+    /// A frozen auction rejects the same instructions as a paused protocol,
+    /// with the same exemptions.
+    pub fn set_auction_frozen(
+        ctx: Context<SetAuctionFrozen>,
+        frozen: bool,
+        reason: u16,
+    ) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+
+        require!(
+            ctx.accounts.config.can_pause(&ctx.accounts.authority.key()),
+            AuctionError::UnauthorizedPauser
+        );
+        auction.frozen = frozen;
+
+        msg!(
+            "Auction frozen: {}, Auction: {}, Reason: {}",
+            frozen,
+            auction.key(),
+            reason
+        );
+        emit!(AuctionFreezeChanged {
+            auction: auction.key(),
+            authority: ctx.accounts.authority.key(),
+            frozen,
+            reason,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Add an approved Arcium MXE cluster to the registry
+    pub fn register_cluster(
+        ctx: Context<RegisterCluster>,
+        cluster_id: u32,
+        mxe_pubkey: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AuctionError::UnauthorizedAdmin
+        );
+
+        let cluster = &mut ctx.accounts.cluster;
+        cluster.cluster_id = cluster_id;
+        cluster.mxe_pubkey = mxe_pubkey;
+        cluster.active = true;
+        cluster.registered_at = Clock::get()?.unix_timestamp;
+        cluster.deprecated_at = None;
+        cluster.bump = ctx.bumps.cluster;
+
+        msg!("MXE cluster registered: {}", cluster_id);
+        emit_cluster_updated(cluster)
+    }
+
+    /// Deprecate a cluster, or reactivate a deprecated one
+    ///
+    /// Deprecation only stops new auctions from selecting the cluster.
+    /// Live auctions keep the key they copied at creation and finalize
+    /// against it, so a key is rotated by registering its replacement and
+    /// deprecating the old cluster.
+    pub fn set_cluster_active(ctx: Context<SetClusterActive>, active: bool) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AuctionError::UnauthorizedAdmin
+        );
+
+        let cluster = &mut ctx.accounts.cluster;
+        cluster.active = active;
+        cluster.deprecated_at = if active {
+            None
+        } else {
+            Some(Clock::get()?.unix_timestamp)
+        };
+
+        msg!("MXE cluster {} active: {}", cluster.cluster_id, active);
+        emit_cluster_updated(cluster)
+    }
+
+    /// Register the `finalize_auction` circuit with this program's MXE
+    ///
+    /// Must run once per deployment before any auction can request
+    /// finalization. The circuit's interface and length come from the
+    /// compiled `build/finalize_auction` artifacts, and its result is
+    /// delivered to `finalize_auction_callback` in the same transaction
+    /// that finalizes the computation.
+    pub fn init_finalize_auction_comp_def(ctx: Context<InitFinalizeAuctionCompDef>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AuctionError::UnauthorizedAdmin
+        );
+
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+
+        msg!("Computation definition initialized: finalize_auction");
+        Ok(())
+    }
+}
+
+/// Emit `ClusterUpdated` with the cluster's current state
+fn emit_cluster_updated(cluster: &Account<MxeCluster>) -> Result<()> {
+    emit!(ClusterUpdated {
+        cluster: cluster.key(),
+        cluster_id: cluster.cluster_id,
+        mxe_pubkey: cluster.mxe_pubkey,
+        active: cluster.active,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Emit `ConfigUpdated` with the config's current settings
+fn emit_config_updated(config: &Account<Config>) -> Result<()> {
+    emit!(ConfigUpdated {
+        admin: config.admin,
+        pause_authority: config.pause_authority,
+        fee_bps: config.fee_bps,
+        fee_recipient: config.fee_recipient,
+        keeper_fee_bps: config.keeper_fee_bps,
+        max_item_name_len: config.max_item_name_len,
+        max_description_len: config.max_description_len,
+        min_duration: config.min_duration,
+        max_duration: config.max_duration,
+        finalization_timeout: config.finalization_timeout,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Pay the keeper bounty to whoever finalized the auction
+///
+/// A caller other than the creator is recorded as the auction's keeper,
+/// earning `keeper_fee_bps` of the protocol fee at settlement. Returns the
+/// bounty paid.
+fn reward_keeper<'info>(
+    auction: &mut Account<'info, Auction>,
+    vault: &Account<'info, Vault>,
+    caller: &AccountInfo<'info>,
+) -> Result<u64> {
+    if caller.key() != auction.creator {
+        auction.keeper = Some(caller.key());
+    }
+    release_from_vault(&vault.to_account_info(), caller, auction.keeper_bounty)?;
+
+    Ok(auction.keeper_bounty)
+}
+
+/// Emit `AuctionFinalized` from the auction's resolved state
+fn emit_finalized(auction: &Account<Auction>) {
+    emit!(AuctionFinalized {
+        auction: auction.key(),
+        creator: auction.creator,
+        status: auction.status.clone(),
+        winner: auction.winner,
+        winning_bid: auction.winning_bid,
+        clearing_price: auction.clearing_price,
+        units_sold: auction.units_sold,
+        bid_count: auction.bid_count,
+        timestamp: auction.finalized_at.unwrap_or_default(),
+    });
+}
+
+/// Revenue split over the verified creators of an asset's metadata
+///
+/// Unverified creators are skipped and the remaining shares scaled up to
+/// sum to 10000 bps, with rounding dust going to the last creator. Empty
+/// when no creator is verified, so the auction creator is paid instead.
+fn verified_creator_shares(creators: &[Creator]) -> Vec<RevenueShare> {
+    let verified: Vec<&Creator> = creators
+        .iter()
+        .filter(|creator| creator.verified && creator.share > 0)
+        .collect();
+    let total: u32 = verified.iter().map(|creator| creator.share as u32).sum();
+
+    let mut remaining = BPS_DENOMINATOR;
+    verified
+        .iter()
+        .enumerate()
+        .map(|(i, creator)| {
+            let bps = if i + 1 == verified.len() {
+                remaining
+            } else {
+                (creator.share as u32 * BPS_DENOMINATOR as u32 / total) as u16
+            };
+            remaining -= bps;
+            RevenueShare {
+                recipient: creator.address,
+                bps,
+            }
+        })
+        .collect()
+}
+
+/// Check a revenue split: empty, or at most `MAX_REVENUE_SHARES` non-zero
+/// shares summing to 100%
+fn validate_revenue_shares(shares: &[RevenueShare]) -> Result<()> {
+    if shares.is_empty() {
+        return Ok(());
+    }
+
+    require!(
+        shares.len() <= MAX_REVENUE_SHARES,
+        AuctionError::TooManyRevenueShares
+    );
+    require!(
+        shares.iter().all(|share| share.bps > 0),
+        AuctionError::InvalidRevenueShares
+    );
+    let total: u32 = shares.iter().map(|share| share.bps as u32).sum();
+    require!(
+        total == BPS_DENOMINATOR as u32,
+        AuctionError::InvalidRevenueShares
+    );
+
+    Ok(())
+}
+
+/// Commitment a commit-reveal bidder submits in place of a ciphertext
+///
+/// `sha256(amount_le || salt || auction || bidder)`. Binding the auction
+/// and bidder keys stops a commitment from being copied into another
+/// auction or replayed by another wallet.
+pub fn bid_commitment(auction: &Pubkey, bidder: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[
+        &amount.to_le_bytes(),
+        salt,
+        auction.as_ref(),
+        bidder.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// Associated data a bid ciphertext is authenticated over: `auction || bidder`
+///
+/// The bid's authentication tag is derived from it (see `bid_tag`), and the
+/// program recomputes it from each `Bid` account when queueing the
+/// finalization circuit, which discards bids whose tag does not verify.
+pub fn bid_associated_data(auction: &Pubkey, bidder: &Pubkey) -> [u8; 64] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(auction.as_ref());
+    data[32..].copy_from_slice(bidder.as_ref());
+    data
+}
+
+/// Authentication tag a bid must carry: the first 16 bytes of
+/// `sha256(bid_associated_data)`, read little-endian
+///
+/// Clients encrypt it as the last block of the bid, and the finalization
+/// circuit rejects a bid that does not decrypt to the tag of its slot.
+pub fn bid_tag(auction: &Pubkey, bidder: &Pubkey) -> u128 {
+    let digest = hash(&bid_associated_data(auction, bidder)).to_bytes();
+    let mut tag = [0u8; 16];
+    tag.copy_from_slice(&digest[..16]);
+    u128::from_le_bytes(tag)
+}
+
+/// Leaf of a bidder allowlist Merkle tree: `sha256(0x00 || wallet)`
+///
+/// Leaves and inner nodes use distinct prefixes so an inner node can never
+/// be passed off as a leaf.
+pub fn allowlist_leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[&[0x00], wallet.as_ref()]).to_bytes()
+}
+
+/// Check a Merkle proof of `leaf` against an allowlist `root`
+///
+/// Inner nodes are `sha256(0x01 || min(a, b) || max(a, b))`, so proofs are
+/// a plain list of sibling hashes without direction bits.
+pub fn verify_allowlist_proof(root: &[u8; 32], leaf: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(*leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling {
+            (&node, sibling)
+        } else {
+            (sibling, &node)
+        };
+        hashv(&[&[0x01], left, right]).to_bytes()
+    });
+
+    computed == *root
+}
+
+/// Check a bidder qualifies under an auction's token gate
+///
+/// `accounts` starts with a token account the bidder owns. For collection
+/// gates it is followed by the token metadata account of that token's mint,
+/// which must belong to the verified collection.
+fn check_bid_gate<'info>(
+    gate: &BidGate,
+    bidder: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let token_account = accounts.first().ok_or(AuctionError::BidGateNotMet)?;
+    let token_account = Account::<TokenAccount>::try_from(token_account)?;
+    require!(token_account.owner == *bidder, AuctionError::BidGateNotMet);
+
+    match gate {
+        BidGate::Token { mint, min_amount } => {
+            require!(
+                token_account.mint == *mint && token_account.amount >= *min_amount,
+                AuctionError::BidGateNotMet
+            );
+        }
+        BidGate::Collection { collection } => {
+            let metadata = accounts.get(1).ok_or(AuctionError::BidGateNotMet)?;
+            let metadata = Account::<MetadataAccount>::try_from(metadata)?;
+            require!(
+                token_account.amount > 0
+                    && metadata.mint == token_account.mint
+                    && metadata
+                        .collection
+                        .as_ref()
+                        .is_some_and(|c| c.verified && c.key == *collection),
+                AuctionError::BidGateNotMet
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply uniform-price allocations from the MPC result to the auction's bids
+///
+/// Each allocation is written to the bidder's `computation_bids` entry, so
+/// winners can later claim their `Allocation` PDA and losers can be told
+/// apart for refunds.
+fn record_allocations(auction: &mut Auction, result: &ComputationResult) -> Result<()> {
+    let price = result.winning_bid;
+    let mut units_sold: u64 = 0;
+
+    if !result.allocations.is_empty() {
+        require!(price >= auction.min_bid, AuctionError::WinningBidTooLow);
+    }
+
+    let mut bids = auction.computation_bids.clone();
+    for allocation in &result.allocations {
+        let bid = bids
+            .iter_mut()
+            .find(|bid| bid.bidder == allocation.bidder)
+            .ok_or(AuctionError::NotWinningBid)?;
+        require!(
+            allocation.quantity > 0 && bid.allocated_quantity == 0,
+            AuctionError::InvalidQuantity
+        );
+        let payment = price
+            .checked_mul(allocation.quantity)
+            .ok_or(AuctionError::InsufficientDeposit)?;
+        require!(payment <= bid.deposit, AuctionError::InsufficientDeposit);
+
+        units_sold = units_sold
+            .checked_add(allocation.quantity)
+            .ok_or(AuctionError::InvalidQuantity)?;
+        bid.allocated_quantity = allocation.quantity;
+    }
+
+    require!(units_sold <= auction.quantity, AuctionError::InvalidQuantity);
+
+    auction.computation_bids = bids;
+    auction.units_sold = units_sold;
+    auction.clearing_price = (units_sold > 0).then_some(price);
+
+    Ok(())
+}
+
+// ============================================================================
+// Arcium Interface
+// ============================================================================
+
+/// Offset of the `finalize_auction` circuit among the MXE's computation definitions
+pub const COMP_DEF_OFFSET_FINALIZE_AUCTION: u32 = comp_def_offset("finalize_auction");
+
+/// Offset of the ciphertext in a `Bid` account: discriminator, auction,
+/// bidder and the ciphertext's length prefix
+const BID_CIPHERTEXT_OFFSET: u32 = 8 + 32 + 32 + 4;
+
+/// Encryption key of empty bid slots and a missing reserve (the x25519 base
+/// point); the circuit never counts what they decrypt to
+const PADDING_X25519_KEY: [u8; 32] = {
+    let mut key = [0u8; 32];
+    key[0] = 9;
+    key
+};
+
+/// Arcium's account macros report an MXE without a cluster through `ErrorCode`
+use AuctionError as ErrorCode;
+
+/// Arguments of the `finalize_auction` circuit for `bids`, in slot order
+///
+/// Each bid contributes its x25519 key and nonce, and the MXE reads its
+/// three ciphertext blocks from the `Bid` account itself. Unused slots are
+/// padded with zero ciphertexts, which the circuit skips as it only counts
+/// the first `bid_count` slots. The plaintext terms of every slot follow:
+/// the tag the bid must decrypt to (see `bid_tag`) and the most it may
+/// commit to pay. Then come the auction's terms and its encrypted reserve.
+fn finalize_auction_args(auction: &Account<Auction>, bids: &[(Pubkey, Bid)]) -> Vec<Argument> {
+    let mut args = Vec::new();
+    for slot in 0..MAX_BIDS_PER_AUCTION as usize {
+        match bids.get(slot) {
+            Some((key, bid)) => {
+                args.push(Argument::ArcisPubkey(bid.x25519_pubkey));
+                args.push(Argument::PlaintextU128(u128::from_le_bytes(bid.nonce)));
+                args.push(Argument::Account(
+                    *key,
+                    BID_CIPHERTEXT_OFFSET,
+                    SEALED_BID_LEN as u32,
+                ));
+            }
+            None => {
+                args.push(Argument::ArcisPubkey(PADDING_X25519_KEY));
+                args.push(Argument::PlaintextU128(0));
+                for _ in 0..SEALED_BID_LEN / 32 {
+                    args.push(Argument::EncryptedU64([0; 32]));
+                }
+            }
+        }
+    }
+
+    for slot in 0..MAX_BIDS_PER_AUCTION as usize {
+        let (tag, max_payment) = match bids.get(slot) {
+            Some((_, bid)) if auction.payment_window > 0 => {
+                (bid_tag(&auction.key(), &bid.bidder), u64::MAX)
+            }
+            Some((_, bid)) => (bid_tag(&auction.key(), &bid.bidder), bid.deposit),
+            None => (0, 0),
+        };
+        args.push(Argument::PlaintextU128(tag));
+        args.push(Argument::PlaintextU64(max_payment));
+    }
+
+    args.push(Argument::PlaintextU8(bids.len() as u8));
+    args.push(Argument::PlaintextU64(auction.min_bid));
+    args.push(Argument::PlaintextU64(auction.quantity));
+    args.push(Argument::PlaintextBool(auction.encrypted_reserve.is_some()));
+    args.push(Argument::PlaintextBool(auction.kind == AuctionKind::SecondPrice));
+    match &auction.encrypted_reserve {
+        Some(reserve) => {
+            args.push(Argument::ArcisPubkey(reserve.x25519_pubkey));
+            args.push(Argument::PlaintextU128(u128::from_le_bytes(reserve.nonce)));
+            args.push(Argument::EncryptedU64(reserve.ciphertext));
+        }
+        None => {
+            args.push(Argument::ArcisPubkey(PADDING_X25519_KEY));
+            args.push(Argument::PlaintextU128(0));
+            args.push(Argument::EncryptedU64([0; 32]));
+        }
+    }
+
+    args
+}
+
+/// Pick the winner of a commit-reveal auction from its bids
+///
+/// The highest revealed bid that meets `min_bid` and is covered by its
+/// deposit wins, with ties going to the earliest bid. Also returns the
+/// total deposit of unrevealed bids, which is forfeited to the creator.
+fn select_reveal_winner(bids: &[Bid], min_bid: u64) -> Result<(Option<&Bid>, u64)> {
+    let mut forfeited: u64 = 0;
+    let mut best: Option<&Bid> = None;
+    for bid in bids {
+        let Some(amount) = bid.revealed_amount else {
+            forfeited = forfeited
+                .checked_add(bid.deposit)
+                .ok_or(AuctionError::InsufficientEscrow)?;
+            continue;
+        };
+        if amount < min_bid || amount > bid.deposit {
+            continue;
+        }
+        let better = match best {
+            Some(current) => {
+                let current_amount = current.revealed_amount.unwrap_or_default();
+                amount > current_amount
+                    || (amount == current_amount && bid.timestamp < current.timestamp)
+            }
+            None => true,
+        };
+        if better {
+            best = Some(bid);
+        }
+    }
+
+    Ok((best, forfeited))
+}
+
+/// Load every bid of an auction from `remaining_accounts`
+///
+/// Requires exactly `auction.bid_count` distinct bid accounts of this
+/// auction, so no bid can be left out of a computation.
+fn load_bids(auction: &Account<Auction>, accounts: &[AccountInfo]) -> Result<Vec<Bid>> {
+    require!(
+        accounts.len() as u64 == auction.bid_count,
+        AuctionError::IncompleteBidSet
+    );
+
+    let mut keys = Vec::with_capacity(accounts.len());
+    let mut bids = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        require!(account.owner == &crate::ID, AuctionError::IncompleteBidSet);
+        let bid = Bid::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(
+            bid.auction == auction.key(),
+            AuctionError::BidAuctionMismatch
+        );
+        keys.push(account.key());
+        bids.push(bid);
+    }
+
+    keys.sort();
+    keys.dedup();
+    require!(keys.len() == accounts.len(), AuctionError::IncompleteBidSet);
+
+    Ok(bids)
+}
+
+// ============================================================================
+// Escrow Helpers
+// ============================================================================
+
+/// Escrow accounts for an auction's quote currency
+///
+/// Lamport auctions hold funds in the program-owned `Vault`. SPL auctions
+/// hold them in the auction's associated token account for `quote_mint`,
+/// which the auction PDA signs for.
+struct Escrow<'a, 'info> {
+    auction: &'a Account<'info, Auction>,
+    vault: &'a Account<'info, Vault>,
+    quote_vault: Option<&'a Account<'info, TokenAccount>>,
+    token_program: &'a Program<'info, Token>,
+}
+
+impl<'a, 'info> Escrow<'a, 'info> {
+    fn new(
+        auction: &'a Account<'info, Auction>,
+        vault: &'a Account<'info, Vault>,
+        quote_vault: Option<&'a Account<'info, TokenAccount>>,
+        token_program: &'a Program<'info, Token>,
+    ) -> Result<Self> {
+        let quote_vault = match auction.quote_mint {
+            Some(mint) => {
+                let quote_vault = quote_vault.ok_or(AuctionError::MissingTokenAccount)?;
+                require!(
+                    quote_vault.key() == get_associated_token_address(&auction.key(), &mint),
+                    AuctionError::InvalidQuoteVault
+                );
+                Some(quote_vault)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            auction,
+            vault,
+            quote_vault,
+            token_program,
+        })
+    }
+
+    /// Lock funds from a payer in escrow
+    fn deposit(
+        &self,
+        payer: &Signer<'info>,
+        payer_token_account: Option<&Account<'info, TokenAccount>>,
+        system_program: &Program<'info, System>,
+        amount: u64,
+    ) -> Result<()> {
+        match self.quote_vault {
+            Some(quote_vault) => {
+                let payer_token_account =
+                    payer_token_account.ok_or(AuctionError::MissingTokenAccount)?;
+                token::transfer(
+                    CpiContext::new(
+                        self.token_program.to_account_info(),
+                        token::Transfer {
+                            from: payer_token_account.to_account_info(),
+                            to: quote_vault.to_account_info(),
+                            authority: payer.to_account_info(),
+                        },
+                    ),
+                    amount,
+                )
+            }
+            None => system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: self.vault.to_account_info(),
+                    },
+                ),
+                amount,
+            ),
+        }
+    }
+
+    /// Release escrowed funds to a wallet, or to its token account for SPL auctions
+    fn release(
+        &self,
+        wallet: &AccountInfo<'info>,
+        wallet_token_account: Option<&Account<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        match self.quote_vault {
+            Some(quote_vault) => {
+                let wallet_token_account =
+                    wallet_token_account.ok_or(AuctionError::MissingTokenAccount)?;
+                require!(
+                    wallet_token_account.owner == wallet.key(),
+                    AuctionError::InvalidTokenAccount
+                );
+
+                transfer_from_auction(
+                    self.auction,
+                    quote_vault,
+                    wallet_token_account,
+                    self.token_program,
+                    amount,
+                )
+            }
+            None => release_from_vault(&self.vault.to_account_info(), wallet, amount),
+        }
+    }
+
+    /// Release escrowed funds to `recipient` through a single unchecked
+    /// account: the wallet itself for lamport auctions, or its token account
+    /// for SPL auctions
+    fn release_to(
+        &self,
+        recipient: &Pubkey,
+        account: &'info AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        match self.quote_vault {
+            Some(quote_vault) => {
+                let token_account = Account::<TokenAccount>::try_from(account)?;
+                require!(
+                    token_account.owner == *recipient,
+                    AuctionError::InvalidTokenAccount
+                );
+
+                transfer_from_auction(
+                    self.auction,
+                    quote_vault,
+                    &token_account,
+                    self.token_program,
+                    amount,
+                )
+            }
+            None => {
+                require!(
+                    account.key() == *recipient,
+                    AuctionError::InvalidRevenueRecipient
+                );
+                release_from_vault(&self.vault.to_account_info(), account, amount)
+            }
+        }
+    }
+}
+
+/// Split `amount` across `shares`, rounding each share down and giving the
+/// dust to the last recipient
+fn revenue_payouts(amount: u64, shares: &[RevenueShare]) -> Vec<u64> {
+    let mut remaining = amount;
+    shares
+        .iter()
+        .enumerate()
+        .map(|(i, share)| {
+            let payout = if i + 1 == shares.len() {
+                remaining
+            } else {
+                (amount as u128 * share.bps as u128 / BPS_DENOMINATOR as u128) as u64
+            };
+            remaining -= payout;
+            payout
+        })
+        .collect()
+}
+
+/// Pay a winning payment, net of fees, out of escrow to the auction's revenue split
+///
+/// Without a split the creator is paid directly. Otherwise `recipients`
+/// holds one account per share, in order: the recipient's wallet for
+/// lamport auctions, or its quote token account for SPL auctions. Rounding
+/// dust goes to the last recipient.
+fn pay_revenue_shares<'info>(
+    escrow: &Escrow<'_, 'info>,
+    creator: &AccountInfo<'info>,
+    creator_token_account: Option<&Account<'info, TokenAccount>>,
+    recipients: &'info [AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    let shares = &escrow.auction.revenue_shares;
+    if shares.is_empty() {
+        return escrow.release(creator, creator_token_account, amount);
+    }
+
+    require!(
+        recipients.len() == shares.len(),
+        AuctionError::InvalidRevenueRecipient
+    );
+
+    let payouts = revenue_payouts(amount, shares);
+    for ((share, account), payout) in shares.iter().zip(recipients).zip(payouts) {
+        escrow.release_to(&share.recipient, account, payout)?;
+    }
+
+    Ok(())
+}
+
+/// Release escrowed asset units to `recipient` (no-op for unbacked auctions)
+fn release_asset<'info>(
+    auction: &Account<'info, Auction>,
+    asset_vault: Option<&Account<'info, TokenAccount>>,
+    recipient: &Pubkey,
+    recipient_asset_account: Option<&Account<'info, TokenAccount>>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let Some(asset_mint) = auction.asset_mint else {
+        return Ok(());
+    };
+
+    let asset_vault = asset_vault.ok_or(AuctionError::MissingTokenAccount)?;
+    let recipient_asset_account =
+        recipient_asset_account.ok_or(AuctionError::MissingTokenAccount)?;
+    require!(
+        asset_vault.key() == get_associated_token_address(&auction.key(), &asset_mint),
+        AuctionError::InvalidAssetVault
+    );
+    require!(
+        recipient_asset_account.owner == *recipient,
+        AuctionError::InvalidTokenAccount
+    );
+
+    transfer_from_auction(
+        auction,
+        asset_vault,
+        recipient_asset_account,
+        token_program,
+        amount,
+    )
+}
+
+/// Close a token account owned by the auction PDA, sending its rent to `creator`
+///
+/// Tokens left in the account, such as dust anyone can send to the
+/// auction's associated token accounts, are first swept to
+/// `creator_token_account` so they cannot block the close.
+fn close_auction_token_account<'info>(
+    auction: &Account<'info, Auction>,
+    account: &Account<'info, TokenAccount>,
+    creator: &Signer<'info>,
+    creator_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    if account.amount > 0 {
+        let creator_token_account =
+            creator_token_account.ok_or(AuctionError::MissingTokenAccount)?;
+        require!(
+            creator_token_account.owner == auction.creator,
+            AuctionError::InvalidTokenAccount
+        );
+        transfer_from_auction(
+            auction,
+            account,
+            creator_token_account,
+            token_program,
+            account.amount,
+        )?;
+    }
+
+    let index = auction.index.to_le_bytes();
+    let seeds = auction.signer_seeds(&index);
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token::CloseAccount {
+            account: account.to_account_info(),
+            destination: creator.to_account_info(),
+            authority: auction.to_account_info(),
+        },
+        &[&seeds],
+    ))
+}
+
+/// Transfer tokens out of a token account owned by the auction PDA
+fn transfer_from_auction<'info>(
+    auction: &Account<'info, Auction>,
+    from: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let index = auction.index.to_le_bytes();
+    let seeds = auction.signer_seeds(&index);
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: auction.to_account_info(),
+            },
+            &[&seeds],
+        ),
+        amount,
+    )
+}
+
+/// Move lamports out of the program-owned vault
+///
+/// The vault is owned by this program, so lamports can be debited directly
+/// without a system program CPI.
+fn release_from_vault(vault: &AccountInfo, recipient: &AccountInfo, amount: u64) -> Result<()> {
+    let vault_balance = vault
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(AuctionError::InsufficientEscrow)?;
+    let recipient_balance = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(AuctionError::InsufficientEscrow)?;
+
+    **vault.try_borrow_mut_lamports()? = vault_balance;
+    **recipient.try_borrow_mut_lamports()? = recipient_balance;
+
+    Ok(())
+}
+
+// ============================================================================
+// Account Structures
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorProfile::INIT_SPACE,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Auction::INIT_SPACE,
+        seeds = [
+            b"auction",
+            creator.key().as_ref(),
+            &creator_profile.auction_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// SPL mint the auction is denominated in (omit for lamports)
+    pub quote_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = quote_mint,
+        associated_token::authority = auction
+    )]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Mint of the auctioned asset (asset-backed auctions only)
+    pub asset_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = asset_mint,
+        associated_token::authority = auction
+    )]
+    pub asset_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Creator's token account holding the asset (asset-backed auctions only)
+    #[account(mut)]
+    pub creator_asset_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token metadata of the asset, used to default the revenue split to
+    /// its creators (optional, asset-backed auctions only)
+    pub asset_metadata: Option<Account<'info, MetadataAccount>>,
+
+    /// Registered MXE cluster bids are encrypted to (omit for commit-reveal auctions)
+    #[account(
+        seeds = [b"cluster", cluster.cluster_id.to_le_bytes().as_ref()],
+        bump = cluster.bump
+    )]
+    pub cluster: Option<Account<'info, MxeCluster>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(encrypted_bid_data: Vec<u8>, bidder_pubkey: [u8; 32])]
+pub struct SubmitBid<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    /// Nonce registry for the bid's x25519 key (omit for commit-reveal auctions)
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + NonceRegistry::INIT_SPACE,
+        seeds = [b"nonce", auction.key().as_ref(), bidder_pubkey.as_ref()],
+        bump
+    )]
+    pub nonce_registry: Option<Account<'info, NonceRegistry>>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Bidder's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub bidder_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(encrypted_bid_data: Vec<u8>, bidder_pubkey: [u8; 32])]
+pub struct UpdateBid<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    /// Nonce registry for the new x25519 key (omit for commit-reveal auctions)
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + NonceRegistry::INIT_SPACE,
+        seeds = [b"nonce", auction.key().as_ref(), bidder_pubkey.as_ref()],
+        bump
+    )]
+    pub nonce_registry: Option<Account<'info, NonceRegistry>>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// Bidder's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub bidder_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[queue_computation_accounts("finalize_auction", authority)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RequestFinalization<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Anyone triggering finalization; pays the Arcium computation fees
+    /// and receives the keeper bounty
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program signer authorizing the computation with Arcium
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = authority,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+    /// CHECK: Arcium mempool, checked by the Arcium program
+    #[account(mut, address = derive_mempool_pda!())]
+    pub mempool_account: UncheckedAccount<'info>,
+
+    /// CHECK: Arcium executing pool, checked by the Arcium program
+    #[account(mut, address = derive_execpool_pda!())]
+    pub executing_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Computation account created by the Arcium program
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    pub computation_account: UncheckedAccount<'info>,
+
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_FINALIZE_AUCTION))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+
+    pub system_program: Program<'info, System>,
+
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[derive(Accounts)]
+pub struct AbortFinalization<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    pub auction: Account<'info, Auction>,
+
+    #[account(mut)]
+    pub bid: Account<'info, Bid>,
+
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReveals<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Anyone triggering finalization; receives the keeper bounty
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[callback_accounts("finalize_auction")]
+#[derive(Accounts)]
+pub struct FinalizeAuctionCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_FINALIZE_AUCTION))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    /// CHECK: Instructions sysvar, read to verify the callback comes from Arcium
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+}
+
+#[derive(Accounts)]
+pub struct PayBalance<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"bid", auction.key().as_ref(), winner.key().as_ref()],
+        bump = winner_bid.bump
+    )]
+    pub winner_bid: Account<'info, Bid>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// Winner's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub winner_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDefault<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Bid of the winner who failed to pay
+    #[account(mut)]
+    pub winner_bid: Account<'info, Bid>,
+
+    /// Bid of the runner-up (`OfferRunnerUp` policy only)
+    pub runner_up_bid: Option<Account<'info, Bid>>,
+
+    /// CHECK: Receives the slashed collateral, validated against `auction.creator`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Creator's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the protocol share, validated against `config.fee_recipient`
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// Fee recipient's quote token account (SPL auctions with a fee only)
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAuction<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's asset vault (asset-backed auctions only)
+    #[account(mut)]
+    pub asset_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Creator's token account receiving the asset (asset-backed auctions only)
+    #[account(mut)]
+    pub creator_asset_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub bid: Account<'info, Bid>,
+
+    /// Bidder's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub bidder_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the refunded deposit, constrained to `bid.bidder`
+    #[account(mut, address = bid.bidder)]
+    pub bidder: UncheckedAccount<'info>,
+
+    /// Anyone triggering the refund
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimProceeds<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub winner_bid: Account<'info, Bid>,
+
+    /// CHECK: Receives the winner's excess deposit, validated against `winner_bid.bidder`
+    #[account(mut)]
+    pub winner: UncheckedAccount<'info>,
+
+    /// Winner's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub winner_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Auction's asset vault (asset-backed auctions only)
+    #[account(mut)]
+    pub asset_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Winner's token account receiving the asset (asset-backed auctions only)
+    #[account(mut)]
+    pub winner_asset_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the winning payment, validated against `auction.creator`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Creator's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Receives the protocol fee, validated against `config.fee_recipient`
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// Fee recipient's quote token account (SPL auctions with a fee only)
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the keeper's share of the fee, validated against `auction.keeper`
+    #[account(mut)]
+    pub keeper: Option<UncheckedAccount<'info>>,
+
+    /// Keeper's quote token account (SPL auctions with a keeper fee only)
+    #[account(mut)]
+    pub keeper_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Creator or winner triggering settlement
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimForfeits<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Creator's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAllocation<'info> {
+    #[account(constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Allocation::INIT_SPACE,
+        seeds = [b"allocation", bid.key().as_ref()],
+        bump
+    )]
+    pub allocation: Account<'info, Allocation>,
+
+    /// CHECK: Receives the excess deposit, validated against `bid.bidder`
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+
+    /// Bidder's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub bidder_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Auction's asset vault (asset-backed auctions only)
+    #[account(mut)]
+    pub asset_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Bidder's token account receiving the units (asset-backed auctions only)
+    #[account(mut)]
+    pub bidder_asset_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the payment, validated against `auction.creator`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Creator's quote token account (SPL auctions only)
+    #[account(mut)]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Receives the protocol fee, validated against `config.fee_recipient`
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// Fee recipient's quote token account (SPL auctions with a fee only)
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the keeper's share of the fee, validated against `auction.keeper`
+    #[account(mut)]
+    pub keeper: Option<UncheckedAccount<'info>>,
+
+    /// Keeper's quote token account (SPL auctions with a keeper fee only)
+    #[account(mut)]
+    pub keeper_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Creator or winner triggering settlement
+    pub authority: Signer<'info>,
+
+    /// Pays for the `Allocation` account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimAsset<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    /// Auction's asset vault (asset-backed auctions only)
+    #[account(mut)]
+    pub asset_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Creator's token account receiving the asset (asset-backed auctions only)
+    #[account(mut)]
+    pub creator_asset_account: Option<Account<'info, TokenAccount>>,
+
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBid<'info> {
+    #[account(mut, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bid", auction.key().as_ref(), bid.bidder.as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    /// CHECK: Receives the bid's rent, constrained to `bid.bidder`
+    #[account(mut, address = bid.bidder)]
+    pub bidder: UncheckedAccount<'info>,
+
+    /// Anyone closing the bid
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAllocation<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"allocation", allocation.bid.as_ref()],
+        bump = allocation.bump
+    )]
+    pub allocation: Account<'info, Allocation>,
+
+    /// CHECK: Receives the allocation's rent, constrained to `allocation.payer`
+    #[account(mut, address = allocation.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Bidder or payer closing the allocation
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseNonceRegistry<'info> {
+    /// CHECK: The registry's auction, which may already be closed; seeds
+    /// tie it to the registry and the handler checks it is resolved
+    pub auction: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            b"nonce",
+            auction.key().as_ref(),
+            nonce_registry.x25519_pubkey.as_ref()
+        ],
+        bump = nonce_registry.bump
+    )]
+    pub nonce_registry: Account<'info, NonceRegistry>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAuction<'info> {
+    #[account(mut, close = creator, constraint = !auction.frozen @ AuctionError::AuctionFrozen)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AuctionError::ProtocolPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Auction's quote token vault (SPL auctions only)
+    #[account(mut)]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Auction's asset vault (asset-backed auctions only)
+    #[account(mut)]
+    pub asset_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Creator's quote token account, receiving what is left in the quote
+    /// vault (SPL auctions with a non-empty quote vault only)
+    #[account(mut)]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Creator's asset token account, receiving what is left in the asset
+    /// vault (asset-backed auctions with a non-empty asset vault only)
+    #[account(mut)]
+    pub creator_asset_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Auction>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ AuctionError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Config admin or pause authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAuctionFrozen<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Config admin or pause authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(cluster_id: u32)]
+pub struct RegisterCluster<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MxeCluster::INIT_SPACE,
+        seeds = [b"cluster", cluster_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub cluster: Account<'info, MxeCluster>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetClusterActive<'info> {
+    #[account(
+        mut,
+        seeds = [b"cluster", cluster.cluster_id.to_le_bytes().as_ref()],
+        bump = cluster.bump
+    )]
+    pub cluster: Account<'info, MxeCluster>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[init_computation_definition_accounts("finalize_auction", admin)]
+#[derive(Accounts)]
+pub struct InitFinalizeAuctionCompDef<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+    /// CHECK: Created by the Arcium program, which checks the address
+    #[account(mut)]
+    pub comp_def_account: UncheckedAccount<'info>,
+
+    pub arcium_program: Program<'info, Arcium>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    /// Creator's public key
+    pub creator: Pubkey,
+
+    /// Position in the creator's auction counter (PDA seed)
+    pub index: u64,
+
+    /// Item being auctioned
+    #[max_len(64)]
+    pub item_name: String,
+
+    /// Description of the item
+    #[max_len(256)]
+    pub description: String,
+
+    /// Minimum bid amount in lamports, or in base units of `quote_mint`
+    pub min_bid: u64,
+
+    /// Auction end timestamp
+    pub end_time: i64,
+
+    /// Auction creation timestamp
+    pub created_at: i64,
+
+    /// Timestamp bidding opens
+    pub start_time: i64,
+
+    /// Current status
+    pub status: AuctionStatus,
+
+    /// Total number of bids
+    pub bid_count: u64,
+
+    /// Cap on `bid_count`
+    pub max_bids: u64,
+
+    /// Arcium MXE cluster public key (for client-side encryption)
+    pub arcium_mxe_pubkey: [u8; 32],
+
+    /// Registry ID of the cluster the key was copied from (None for commit-reveal)
+    pub cluster_id: Option<u32>,
+
+    /// Fixed deposit required per bid in quote units (0 = bidder-sized deposits)
+    pub collateral: u64,
+
+    /// SPL mint the auction is denominated in (None = lamports)
+    pub quote_mint: Option<Pubkey>,
+
+    /// Pricing rule applied at finalization
+    pub kind: AuctionKind,
+
+    /// Reserve price encrypted to the MXE, never revealed on-chain
+    pub encrypted_reserve: Option<EncryptedValue>,
+
+    /// Units for sale (1 unless the auction is uniform-price)
+    pub quantity: u64,
+
+    /// Units allocated to winners (uniform-price auctions only)
+    pub units_sold: u64,
+
+    /// End of the reveal phase (commit-reveal auctions only)
+    pub reveal_end_time: Option<i64>,
+
+    /// Deposits of unrevealed bids owed to the creator (commit-reveal auctions only)
+    pub forfeited_deposits: u64,
+
+    /// Whether the creator has claimed the forfeited deposits
+    pub forfeits_claimed: bool,
+
+    /// Seconds before `end_time` in which a bid extends the auction (0 = no soft close)
+    pub extension_window: i64,
+
+    /// Seconds a late bid adds to `end_time`
+    pub extension_duration: i64,
+
+    /// Cap on the total extension in seconds
+    pub max_extension: i64,
+
+    /// Total seconds `end_time` has been extended by
+    pub extended_by: i64,
+
+    /// Whether bidders may retract their bids while the auction is active
+    pub allow_withdrawals: bool,
+
+    /// Protocol fee taken from the winning payment, fixed from `Config` at creation
+    pub fee_bps: u16,
+
+    /// Keeper's share of the protocol fee in basis points, fixed from `Config` at creation
+    pub keeper_fee_bps: u16,
+
+    /// Lamports the creator locked in the vault for whoever finalizes the auction
+    pub keeper_bounty: u64,
+
+    /// Third party that finalized the auction (None if the creator did)
+    pub keeper: Option<Pubkey>,
+
+    /// Seconds a winner has to pay the balance above their collateral
+    /// (0 = bids are fully funded)
+    pub payment_window: i64,
+
+    /// What happens when the winner fails to pay in time
+    pub default_policy: DefaultPolicy,
+
+    /// Split of the net proceeds (empty = everything to the creator)
+    #[max_len(5)]
+    pub revenue_shares: Vec<RevenueShare>,
+
+    /// Whether the admin has frozen this auction
+    pub frozen: bool,
+
+    /// Merkle root of the wallets allowed to bid (None = public auction)
+    pub allowlist_root: Option<[u8; 32]>,
+
+    /// Holdings required to bid (None = no token gate)
+    pub bid_gate: Option<BidGate>,
+
+    /// Number of bid accounts closed after resolution
+    pub closed_bid_count: u64,
+
+    /// Winner's public key (revealed after finalization)
+    pub winner: Option<Pubkey>,
+
+    /// Highest bid amount (revealed after finalization)
+    pub winning_bid: Option<u64>,
+
+    /// Amount the winner pays, which differs from `winning_bid` in
+    /// second-price auctions (revealed after finalization)
+    pub clearing_price: Option<u64>,
+
+    /// MPC computation ID from Arcium
+    #[max_len(64)]
+    pub mpc_computation_id: Option<String>,
+
+    /// Arcium computation account of the queued finalization
+    pub computation_account: Option<Pubkey>,
+
+    /// Bids in the circuit's slot order, as queued by `request_finalization`
+    #[max_len(MAX_BIDS_PER_AUCTION)]
+    pub computation_bids: Vec<ComputationBid>,
+
+    /// Time after which a pending finalization can be aborted
+    pub finalization_deadline: Option<i64>,
+
+    /// Time by which the current winner must pay the balance
+    pub payment_deadline: Option<i64>,
+
+    /// Second-ranked bidder from the MPC result (partially funded auctions only)
+    pub runner_up: Option<Pubkey>,
+
+    /// Runner-up's bid amount, the price they are offered on a default
+    pub runner_up_bid: Option<u64>,
+
+    /// Finalization timestamp
+    pub finalized_at: Option<i64>,
+
+    /// Whether the creator has claimed the winning payment
+    pub proceeds_claimed: bool,
+
+    /// Mint of the escrowed asset (None = unbacked auction)
+    pub asset_mint: Option<Pubkey>,
+
+    /// Units of `asset_mint` held in the asset vault
+    pub asset_amount: u64,
+
+    /// Whether the escrowed asset has left the vault
+    pub asset_released: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Auction {
+    /// Entry of `bidder` among the bids queued for finalization
+    pub fn computation_bid(&self, bidder: &Pubkey) -> Option<&ComputationBid> {
+        self.computation_bids.iter().find(|bid| bid.bidder == *bidder)
+    }
+
+    /// Units the MPC result allocated to `bidder` (uniform-price auctions only)
+    pub fn allocated_quantity(&self, bidder: &Pubkey) -> u64 {
+        self.computation_bid(bidder)
+            .map_or(0, |bid| bid.allocated_quantity)
+    }
+
+    /// Check the auction is open for new or updated bids at `now`
+    pub fn require_accepting_bids(&mut self, now: i64) -> Result<()> {
+        // Scheduled auctions open for bids at start_time
+        self.refresh_status(now);
+        require!(
+            self.status != AuctionStatus::Upcoming,
+            AuctionError::AuctionNotStarted
+        );
+        require!(
+            self.status == AuctionStatus::Active,
+            AuctionError::AuctionNotActive
+        );
+        require!(now < self.end_time, AuctionError::AuctionEnded);
+
+        Ok(())
+    }
+
+    /// Move an upcoming auction to `Active` once `start_time` has passed
+    pub fn refresh_status(&mut self, now: i64) {
+        if self.status == AuctionStatus::Upcoming && now >= self.start_time {
+            self.status = AuctionStatus::Active;
+        }
+    }
+
+    /// Extend `end_time` for a bid placed at `now` inside the soft-close window
+    ///
+    /// Returns the previous end time when the auction was extended. The
+    /// reveal phase of commit-reveal auctions shifts by the same amount.
+    pub fn apply_soft_close(&mut self, now: i64) -> Option<i64> {
+        if self.extension_window == 0 || self.end_time - now > self.extension_window {
+            return None;
+        }
+
+        let extension = self
+            .extension_duration
+            .min(self.max_extension - self.extended_by);
+        if extension <= 0 {
+            return None;
+        }
+
+        let previous_end_time = self.end_time;
+        self.end_time += extension;
+        self.extended_by += extension;
+        if let Some(reveal_end_time) = self.reveal_end_time.as_mut() {
+            *reveal_end_time += extension;
+        }
+
+        Some(previous_end_time)
+    }
+
+    /// Whether the auction has reached a terminal state and funds can move
+    pub fn is_resolved(&self) -> bool {
+        matches!(
+            self.status,
+            AuctionStatus::Finalized
+                | AuctionStatus::Cancelled
+                | AuctionStatus::ReserveNotMet
+                | AuctionStatus::Failed
+                | AuctionStatus::Defaulted
+        )
+    }
+
+    /// Asset units backing a single unit for sale
+    pub fn units_per_item(&self) -> u64 {
+        self.asset_amount / self.quantity
+    }
+
+    /// Asset units that return to the creator once the auction is unsold
+    pub fn unsold_asset_amount(&self) -> u64 {
+        match self.kind {
+            AuctionKind::UniformPrice if self.status == AuctionStatus::Finalized => {
+                (self.quantity - self.units_sold) * self.units_per_item()
+            }
+            _ => self.asset_amount,
+        }
+    }
+
+    /// Whether every payout is done and every bid account has been closed
+    pub fn is_settlement_complete(&self) -> bool {
+        let proceeds_done = self.winner.is_none() || self.proceeds_claimed;
+        let asset_done = self.asset_mint.is_none() || self.asset_released;
+        let forfeits_done = self.forfeited_deposits == 0 || self.forfeits_claimed;
+
+        proceeds_done && asset_done && forfeits_done && self.closed_bid_count == self.bid_count
+    }
+
+    /// Whether the auction resolved without selling the item
+    pub fn is_unsold(&self) -> bool {
+        match self.status {
+            AuctionStatus::Finalized => self.winner.is_none(),
+            AuctionStatus::Cancelled
+            | AuctionStatus::ReserveNotMet
+            | AuctionStatus::Failed
+            | AuctionStatus::Defaulted => true,
+            _ => false,
+        }
+    }
+
+    /// Share of `payment` owed to the protocol fee recipient
+    pub fn protocol_fee(&self, payment: u64) -> u64 {
+        (payment as u128 * self.fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Split a defaulting winner's slashed `deposit` into the creator's and
+    /// the protocol's shares
+    pub fn slash_split(&self, deposit: u64) -> (u64, u64) {
+        let protocol_share = self.protocol_fee(deposit);
+        (deposit - protocol_share, protocol_share)
+    }
+
+    /// Move an `AwaitingPayment` auction on after its winner defaulted
+    ///
+    /// Under `OfferRunnerUp` with a runner-up left, the item passes to them
+    /// at their own bid; `runner_up_bid` must then be their `(bidder,
+    /// deposit)`. They get a new payment window when the deposit does not
+    /// cover the price, otherwise the auction is finalized. Without a
+    /// runner-up the auction is `Defaulted`. Returns the new winner.
+    pub fn resolve_default(
+        &mut self,
+        runner_up_bid: Option<(Pubkey, u64)>,
+        now: i64,
+    ) -> Result<Option<Pubkey>> {
+        let runner_up = match self.default_policy {
+            DefaultPolicy::OfferRunnerUp => self.runner_up.take().zip(self.runner_up_bid.take()),
+            DefaultPolicy::FailAuction => None,
+        };
+        let Some((runner_up, price)) = runner_up else {
+            self.winner = None;
+            self.clearing_price = None;
+            self.status = AuctionStatus::Defaulted;
+            return Ok(None);
+        };
+
+        let (bidder, deposit) = runner_up_bid.ok_or(AuctionError::InvalidRunnerUp)?;
+        require!(bidder == runner_up, AuctionError::InvalidRunnerUp);
+
+        self.winner = Some(runner_up);
+        self.winning_bid = Some(price);
+        self.clearing_price = Some(price);
+        if price > deposit {
+            self.payment_deadline = Some(now.saturating_add(self.payment_window));
+        } else {
+            self.payment_deadline = None;
+            self.status = AuctionStatus::Finalized;
+        }
+
+        Ok(Some(runner_up))
+    }
+
+    /// Share of a protocol `fee` owed to the auction's keeper, if any
+    pub fn keeper_fee(&self, fee: u64) -> u64 {
+        if self.keeper.is_none() {
+            return 0;
+        }
+        (fee as u128 * self.keeper_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Price the winner pays under this auction's pricing rule
+    ///
+    /// Second-price auctions charge the runner-up bid, or `min_bid` when
+    /// there was only one valid bid. With an encrypted reserve the circuit
+    /// already floors `second_bid` at the reserve.
+    pub fn clearing_price(&self, highest_bid: u64, second_bid: Option<u64>) -> u64 {
+        match self.kind {
+            AuctionKind::FirstPrice => highest_bid,
+            AuctionKind::SecondPrice => second_bid.unwrap_or(self.min_bid).max(self.min_bid),
+            AuctionKind::UniformPrice | AuctionKind::CommitReveal => highest_bid,
+        }
+    }
+
+    /// Seeds the auction PDA signs with when moving escrowed tokens
+    ///
+    /// `index` must be `self.index.to_le_bytes()`.
+    pub fn signer_seeds<'a>(&'a self, index: &'a [u8; 8]) -> [&'a [u8]; 4] {
+        [
+            b"auction",
+            self.creator.as_ref(),
+            index,
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Protocol-wide settings, a singleton PDA at `[b"config"]`
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// Authority allowed to update the config
+    pub admin: Pubkey,
+
+    /// Authority allowed to pause the protocol and freeze auctions,
+    /// alongside the admin (e.g. an incident-response multisig)
+    pub pause_authority: Pubkey,
+
+    /// Whether the protocol is paused
+    pub paused: bool,
+
+    /// Protocol fee on winning payments, in basis points
+    pub fee_bps: u16,
+
+    /// Wallet receiving protocol fees
+    pub fee_recipient: Pubkey,
+
+    /// Share of the protocol fee paid to third-party finalizers, in basis points
+    pub keeper_fee_bps: u16,
+
+    /// Maximum item name length in bytes (at most 64)
+    pub max_item_name_len: u16,
+
+    /// Maximum description length in bytes (at most 256)
+    pub max_description_len: u16,
+
+    /// Shortest allowed bidding period in seconds
+    pub min_duration: i64,
+
+    /// Longest allowed bidding period in seconds
+    pub max_duration: i64,
+
+    /// Seconds the MPC cluster has to deliver a finalization result, and
+    /// that an ended auction can wait for someone to request it
+    pub finalization_timeout: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Config {
+    /// Item name capacity of the `Auction` account
+    pub const ITEM_NAME_CAPACITY: u16 = 64;
+
+    /// Description capacity of the `Auction` account
+    pub const DESCRIPTION_CAPACITY: u16 = 256;
+
+    /// Check the settings are internally consistent and fit the account layout
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.fee_bps <= BPS_DENOMINATOR && self.keeper_fee_bps <= BPS_DENOMINATOR,
+            AuctionError::InvalidFeeBps
+        );
+        require!(
+            self.max_item_name_len > 0
+                && self.max_item_name_len <= Self::ITEM_NAME_CAPACITY
+                && self.max_description_len <= Self::DESCRIPTION_CAPACITY,
+            AuctionError::InvalidConfig
+        );
+        require!(
+            self.min_duration > 0 && self.min_duration <= self.max_duration,
+            AuctionError::InvalidConfig
+        );
+        require!(self.finalization_timeout > 0, AuctionError::InvalidConfig);
+
+        Ok(())
+    }
+
+    /// Whether `authority` may pause the protocol or freeze auctions
+    pub fn can_pause(&self, authority: &Pubkey) -> bool {
+        *authority == self.admin || *authority == self.pause_authority
+    }
+
+    /// Whether a bidding period of `duration` seconds is within bounds
+    pub fn is_valid_duration(&self, duration: i64) -> bool {
+        (self.min_duration..=self.max_duration).contains(&duration)
+    }
+}
+
+/// An approved Arcium MXE cluster, a PDA at `[b"cluster", cluster_id]`
+#[account]
+#[derive(InitSpace)]
+pub struct MxeCluster {
+    /// Arcium cluster ID
+    pub cluster_id: u32,
+
+    /// MXE public key bids are encrypted to
+    pub mxe_pubkey: [u8; 32],
+
+    /// Whether new auctions may use the cluster
+    pub active: bool,
+
+    /// Registration timestamp
+    pub registered_at: i64,
+
+    /// Timestamp the cluster was deprecated (None while active)
+    pub deprecated_at: Option<i64>,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorProfile {
+    /// Creator's public key
+    pub creator: Pubkey,
+
+    /// Number of auctions created, and the index of the next auction
+    pub auction_count: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Bid {
+    /// Reference to auction
+    pub auction: Pubkey,
+
+    /// Bidder's wallet public key
+    pub bidder: Pubkey,
+
+    /// Encrypted bid data (output of Rescue cipher)
+    /// Contains the encrypted (price, quantity) pair followed by the
+    /// authentication tag; single-item bids have a quantity of 1
+    #[max_len(96)]
+    pub encrypted_data: Vec<u8>,
+
+    /// Ephemeral x25519 public key used for encryption
+    /// Used in key exchange with Arcium MXE public key
+    pub x25519_pubkey: [u8; 32],
+
+    /// Nonce used for Rescue cipher encryption
+    pub nonce: [u8; 16],
+
+    /// Submission timestamp, refreshed on every update
+    pub timestamp: i64,
+
+    /// Amount locked in escrow for this bid, in quote units
+    pub deposit: u64,
+
+    /// Whether the deposit has been refunded or applied to settlement
+    pub settled: bool,
+
+    /// Opened bid amount (commit-reveal auctions only)
+    pub revealed_amount: Option<u64>,
+
+    /// Number of times the bid has been replaced via `update_bid`
+    pub revision: u32,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Allocation {
+    /// Reference to auction
+    pub auction: Pubkey,
+
+    /// Winning bidder
+    pub bidder: Pubkey,
+
+    /// Bid the allocation was made to
+    pub bid: Pubkey,
+
+    /// Units allocated to the bidder
+    pub quantity: u64,
+
+    /// Uniform clearing price paid per unit
+    pub price: u64,
+
+    /// Claim timestamp
+    pub claimed_at: i64,
+
+    /// Account that funded the allocation and receives its rent on close
+    pub payer: Pubkey,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Nonces used with one x25519 key in one auction
+///
+/// The first wallet to bid with a key owns it for the auction, and every
+/// later use must carry a strictly greater nonce (read as little-endian),
+/// so a (key, nonce) pair can never be replayed.
+#[account]
+#[derive(InitSpace)]
+pub struct NonceRegistry {
+    /// Auction the key is registered in
+    pub auction: Pubkey,
+
+    /// Ephemeral x25519 public key
+    pub x25519_pubkey: [u8; 32],
+
+    /// Wallet that registered the key
+    pub bidder: Pubkey,
+
+    /// Highest nonce used with the key
+    pub last_nonce: [u8; 16],
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl NonceRegistry {
+    /// Record a use of `nonce` by `bidder`, rejecting reuse and foreign keys
+    pub fn record(
+        &mut self,
+        auction: Pubkey,
+        bidder: Pubkey,
+        x25519_pubkey: [u8; 32],
+        nonce: [u8; 16],
+        bump: u8,
+    ) -> Result<()> {
+        if self.bidder == Pubkey::default() {
+            self.auction = auction;
+            self.x25519_pubkey = x25519_pubkey;
+            self.bidder = bidder;
+            self.bump = bump;
+        } else {
+            require!(self.bidder == bidder, AuctionError::KeyRegisteredToOtherBidder);
+            require!(
+                u128::from_le_bytes(nonce) > u128::from_le_bytes(self.last_nonce),
+                AuctionError::NonceReused
+            );
+        }
+        self.last_nonce = nonce;
+
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    /// Auction whose bid deposits this vault holds
+    pub auction: Pubkey,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateAuctionParams {
+    /// Item being auctioned
+    pub item_name: String,
+
+    /// Description of the item
+    pub description: String,
+
+    /// Minimum bid amount in quote units
+    pub min_bid: u64,
+
+    /// Timestamp bidding opens (None = immediately)
+    pub start_time: Option<i64>,
+
+    /// Auction end timestamp
+    pub end_time: i64,
+
+    /// Maximum number of open bids, at most `MAX_BIDS_PER_AUCTION`
+    pub max_bids: u64,
+
+    /// Fixed deposit per bid, 0 = bidder-sized deposits
+    pub collateral: u64,
+
+    /// Units of the asset mint to escrow (asset-backed auctions only)
+    pub asset_amount: u64,
+
+    /// Pricing rule applied at finalization
+    pub kind: AuctionKind,
+
+    /// Reserve price encrypted to the MXE like a bid (optional, not for
+    /// commit-reveal auctions)
+    pub encrypted_reserve: Option<EncryptedValue>,
+
+    /// Units for sale (uniform-price auctions only)
+    pub quantity: u64,
+
+    /// End of the reveal phase (commit-reveal auctions only)
+    pub reveal_end_time: Option<i64>,
+
+    /// Seconds before `end_time` in which a bid extends the auction (0 = no soft close)
+    pub extension_window: i64,
+
+    /// Seconds a late bid adds to `end_time`
+    pub extension_duration: i64,
+
+    /// Cap on the total extension in seconds
+    pub max_extension: i64,
+
+    /// Whether bidders may retract their bids while the auction is active
+    pub allow_withdrawals: bool,
+
+    /// Lamports paid to whoever finalizes the auction (0 = no bounty)
+    pub keeper_bounty: u64,
+
+    /// Seconds the winner has to pay the balance above the collateral
+    /// (0 = fully funded bids; fixed-collateral first/second-price only)
+    pub payment_window: i64,
+
+    /// What happens when the winner fails to pay in time
+    pub default_policy: DefaultPolicy,
+
+    /// Split of the net proceeds in basis points summing to 10000, or
+    /// empty to pay the creator (or the asset's verified metadata creators)
+    pub revenue_shares: Vec<RevenueShare>,
+
+    /// Merkle root of the wallets allowed to bid (None = public auction)
+    pub allowlist_root: Option<[u8; 32]>,
+
+    /// Holdings required to bid (None = no token gate)
+    pub bid_gate: Option<BidGate>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateAuctionParams {
+    /// New name of the item
+    pub item_name: Option<String>,
+
+    /// New description of the item
+    pub description: Option<String>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    /// Authority allowed to pause the protocol alongside the admin
+    pub pause_authority: Pubkey,
+
+    /// Protocol fee on winning payments, in basis points
+    pub fee_bps: u16,
+
+    /// Wallet receiving protocol fees
+    pub fee_recipient: Pubkey,
+
+    /// Share of the protocol fee paid to third-party finalizers, in basis points
+    pub keeper_fee_bps: u16,
+
+    /// Maximum item name length in bytes
+    pub max_item_name_len: u16,
+
+    /// Maximum description length in bytes
+    pub max_description_len: u16,
+
+    /// Shortest allowed bidding period in seconds
+    pub min_duration: i64,
+
+    /// Longest allowed bidding period in seconds
+    pub max_duration: i64,
+
+    /// Seconds the MPC cluster has to deliver a finalization result, and
+    /// that an ended auction can wait for someone to request it
+    pub finalization_timeout: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateConfigParams {
+    /// New config admin
+    pub admin: Option<Pubkey>,
+
+    /// New pause authority
+    pub pause_authority: Option<Pubkey>,
+
+    /// New protocol fee in basis points
+    pub fee_bps: Option<u16>,
+
+    /// New fee recipient
+    pub fee_recipient: Option<Pubkey>,
+
+    /// New keeper share of the protocol fee
+    pub keeper_fee_bps: Option<u16>,
+
+    /// New maximum item name length
+    pub max_item_name_len: Option<u16>,
+
+    /// New maximum description length
+    pub max_description_len: Option<u16>,
+
+    /// New shortest bidding period
+    pub min_duration: Option<i64>,
+
+    /// New longest bidding period
+    pub max_duration: Option<i64>,
+
+    /// New finalization timeout, applied to later finalization requests
+    pub finalization_timeout: Option<i64>,
+}
+
+/// Result of the finalization circuit, decoded by `finalize_auction_callback`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ComputationResult {
+    /// Winning bidder (None when no valid bid met `min_bid`)
+    pub winner: Option<Pubkey>,
+
+    /// Highest valid bid amount, or the clearing price for uniform-price auctions
+    pub winning_bid: u64,
+
+    /// Second-highest valid bid amount (None when there was only one)
+    ///
+    /// In second-price auctions with an encrypted reserve this is
+    /// `max(second-highest bid, reserve)`, and set whenever there is a
+    /// winner, so a lone bid above the reserve pays the reserve.
+    pub second_bid: Option<u64>,
+
+    /// Bidder ranked second, who placed `second_bid`
+    pub runner_up: Option<Pubkey>,
+
+    /// Whether the highest bid met the encrypted reserve
+    /// (ignored when the auction has no reserve)
+    pub reserve_met: bool,
+
+    /// Winners and their allocated units (uniform-price auctions only)
+    pub allocations: Vec<UnitAllocation>,
+
+    /// Bids discarded because their authentication tag did not verify
+    pub rejected_bids: u32,
+}
+
+impl ComputationResult {
+    /// Decode the revealed outcome of the `finalize_auction` circuit
+    ///
+    /// The circuit refers to bids by slot, which are mapped back to bidders
+    /// through the auction's `computation_bids`. Uniform-price results carry
+    /// allocations with the clearing price as `winning_bid`; single-item
+    /// results a winner and runner-up.
+    pub fn from_outcome(auction: &Auction, outcome: &FinalizeAuctionOutputStruct0) -> Result<Self> {
+        let FinalizeAuctionOutputStruct0 {
+            field_0: reserve_met,
+            field_1: has_winner,
+            field_2: winner,
+            field_3: winning_bid,
+            field_4: has_second,
+            field_5: runner_up,
+            field_6: second_bid,
+            field_7: clearing_price,
+            field_8: allocations,
+            field_9: rejected_bids,
+            field_10: has_runner_up,
+        } = *outcome;
+        let bidder = |slot: usize| {
+            auction
+                .computation_bids
+                .get(slot)
+                .map(|bid| bid.bidder)
+                .ok_or(error!(AuctionError::InvalidComputationResult))
+        };
+
+        if auction.kind == AuctionKind::UniformPrice {
+            let allocations = allocations
+                .iter()
+                .enumerate()
+                .filter(|(_, &quantity)| quantity > 0)
+                .map(|(slot, &quantity)| {
+                    Ok(UnitAllocation {
+                        bidder: bidder(slot)?,
+                        quantity,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(Self {
+                winner: None,
+                winning_bid: clearing_price,
+                second_bid: None,
+                runner_up: None,
+                reserve_met,
+                allocations,
+                rejected_bids: u32::from(rejected_bids),
+            });
+        }
+
+        Ok(Self {
+            winner: has_winner.then(|| bidder(winner as usize)).transpose()?,
+            winning_bid: if has_winner { winning_bid } else { 0 },
+            second_bid: has_second.then_some(second_bid),
+            runner_up: has_runner_up.then(|| bidder(runner_up as usize)).transpose()?,
+            reserve_met,
+            allocations: Vec::new(),
+            rejected_bids: u32::from(rejected_bids),
+        })
+    }
+}
+
+/// A bid as queued for the finalization circuit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ComputationBid {
+    /// Bidder whose bid fills the slot
+    pub bidder: Pubkey,
+
+    /// Deposit locked for the bid when the computation was queued
+    pub deposit: u64,
+
+    /// Units allocated by the MPC result (uniform-price auctions only)
+    pub allocated_quantity: u64,
+}
+
+/// Units allocated to one bidder by a uniform-price computation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UnitAllocation {
+    pub bidder: Pubkey,
+    pub quantity: u64,
+}
+
+/// Length of the authentication tag appended to bid ciphertexts
+pub const BID_TAG_LEN: usize = 32;
+
+/// Length of an encrypted bid: price and quantity blocks followed by the tag
+pub const SEALED_BID_LEN: usize = 64 + BID_TAG_LEN;
+
+/// Maximum number of bids per auction, so `request_finalization` with every
+/// bid account still fits in a single legacy transaction (1,232 bytes).
+/// Must match `MAX_BIDS` in the `finalize_auction` circuit.
+pub const MAX_BIDS_PER_AUCTION: u64 = 16;
+
+/// Maximum allowlist proof length, enough for trees of over a million wallets
+pub const MAX_ALLOWLIST_PROOF_LEN: usize = 20;
+
+/// Maximum number of revenue split recipients, matching the metadata creator limit
+pub const MAX_REVENUE_SHARES: usize = 5;
+
+/// A recipient's share of an auction's net proceeds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct RevenueShare {
+    /// Wallet receiving the share
+    pub recipient: Pubkey,
+
+    /// Share of the proceeds in basis points
+    pub bps: u16,
+}
+
+/// A value encrypted client-side to the auction's MXE public key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct EncryptedValue {
+    /// Rescue cipher output
+    pub ciphertext: [u8; 32],
+
+    /// Ephemeral x25519 public key used for encryption
+    pub x25519_pubkey: [u8; 32],
+
+    /// Nonce used for Rescue cipher encryption
+    pub nonce: [u8; 16],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AuctionKind {
+    /// Highest bidder wins and pays their own bid
+    FirstPrice,
+    /// Highest bidder wins and pays the second-highest bid (Vickrey)
+    SecondPrice,
+    /// Multiple identical units; every winner pays one clearing price
+    UniformPrice,
+    /// Hash commitments opened after bidding closes; resolved on-chain
+    /// without an MPC cluster
+    CommitReveal,
+}
+
+impl AuctionKind {
+    /// Length of a bid ciphertext: a sealed bid for the MPC auction kinds,
+    /// a bare hash commitment for commit-reveal
+    pub fn bid_ciphertext_len(&self) -> usize {
+        match self {
+            AuctionKind::CommitReveal => 32,
+            _ => SEALED_BID_LEN,
+        }
+    }
+}
+
+/// Resolution of a partially funded auction whose winner fails to pay
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DefaultPolicy {
+    /// The auction is declared `Defaulted`
+    FailAuction,
+    /// The runner-up is offered the item at their own bid
+    OfferRunnerUp,
+}
+
+/// Holdings a wallet needs to bid in a token-gated auction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum BidGate {
+    /// At least `min_amount` base units of `mint`
+    Token { mint: Pubkey, min_amount: u64 },
+    /// Any NFT verified as a member of `collection`
+    Collection { collection: Pubkey },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum AuctionStatus {
+    Active,
+    Finalized,
+    Cancelled,
+    Finalizing,
+    ReserveNotMet,
+    Upcoming,
+    /// The MPC result did not arrive before the finalization deadline
+    Failed,
+    /// Waiting for the winner to pay the balance above their collateral
+    AwaitingPayment,
+    /// No winner paid in time; the auction resolved unsold
+    Defaulted,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct AuctionCreated {
+    pub auction: Pubkey,
+    pub creator: Pubkey,
+    pub index: u64,
+    pub kind: AuctionKind,
+    pub min_bid: u64,
+    pub quantity: u64,
+    pub max_bids: u64,
+    pub quote_mint: Option<Pubkey>,
+    pub asset_mint: Option<Pubkey>,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionUpdated {
+    pub auction: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidSubmitted {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub deposit: u64,
+    pub bid_count: u64,
+    pub end_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidUpdated {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub revision: u32,
+    pub end_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidWithdrawn {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub refund: u64,
+    pub bid_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidRevealed {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FinalizationRequested {
+    pub auction: Pubkey,
+    pub requester: Pubkey,
+    pub keeper_reward: u64,
+    pub computation_account: Pubkey,
+    pub bid_count: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted for every resolution of a bid-taking auction, including
+/// `ReserveNotMet` outcomes
+#[event]
+pub struct AuctionFinalized {
+    pub auction: Pubkey,
+    pub creator: Pubkey,
+    pub status: AuctionStatus,
+    pub winner: Option<Pubkey>,
+    pub winning_bid: Option<u64>,
+    pub clearing_price: Option<u64>,
+    pub units_sold: u64,
+    pub bid_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FinalizationAborted {
+    pub auction: Pubkey,
+    pub caller: Pubkey,
+    pub computation_account: Option<Pubkey>,
+    pub mpc_computation_id: Option<String>,
+    pub finalization_deadline: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalancePaid {
+    pub auction: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentDefaulted {
+    pub auction: Pubkey,
+    pub defaulted_bidder: Pubkey,
+    pub slashed: u64,
+    pub protocol_share: u64,
+    pub next_winner: Option<Pubkey>,
+    pub status: AuctionStatus,
+    pub payment_deadline: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub auction: Pubkey,
+    pub creator: Pubkey,
+    pub asset_returned: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositRefunded {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProceedsClaimed {
+    pub auction: Pubkey,
+    pub creator: Pubkey,
+    pub winner: Pubkey,
+    pub price: u64,
+    pub protocol_fee: u64,
+    pub keeper_fee: u64,
+    pub winner_refund: u64,
+    pub asset_released: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetReclaimed {
+    pub auction: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForfeitsClaimed {
+    pub auction: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllocationClaimed {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub quantity: u64,
+    pub price: u64,
+    pub payment: u64,
+    pub protocol_fee: u64,
+    pub keeper_fee: u64,
+    pub refund: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidClosed {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub closed_bid_count: u64,
+    pub bid_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionClosed {
+    pub auction: Pubkey,
+    pub creator: Pubkey,
+    pub bid_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub pause_authority: Pubkey,
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub keeper_fee_bps: u16,
+    pub max_item_name_len: u16,
+    pub max_description_len: u16,
+    pub min_duration: i64,
+    pub max_duration: i64,
+    pub finalization_timeout: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClusterUpdated {
+    pub cluster: Pubkey,
+    pub cluster_id: u32,
+    pub mxe_pubkey: [u8; 32],
+    pub active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPauseChanged {
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub reason: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionFreezeChanged {
+    pub auction: Pubkey,
+    pub authority: Pubkey,
+    pub frozen: bool,
+    pub reason: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionExtended {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub previous_end_time: i64,
+    pub new_end_time: i64,
+    pub total_extension: i64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum AuctionStatus {
-    Active,
-    Finalized,
-    Cancelled,
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[error_code]
+pub enum AuctionError {
+    #[msg("Auction end time must be in the future")]
+    InvalidEndTime,
+
+    #[msg("Minimum bid must be greater than 0")]
+    InvalidMinBid,
+
+    #[msg("Item name exceeds the configured maximum length")]
+    ItemNameTooLong,
+
+    #[msg("Description exceeds the configured maximum length")]
+    DescriptionTooLong,
+
+    #[msg("Auction is not active")]
+    AuctionNotActive,
+
+    #[msg("Auction has already ended")]
+    AuctionEnded,
+
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+
+    #[msg("Invalid encrypted data length")]
+    InvalidEncryptedData,
+
+    #[msg("Invalid x25519 public key")]
+    InvalidPublicKey,
+
+    #[msg("Invalid encryption nonce")]
+    InvalidNonce,
+
+    #[msg("Unauthorized to finalize this auction")]
+    UnauthorizedFinalizer,
+
+    #[msg("Winning bid is below minimum bid")]
+    WinningBidTooLow,
+
+    #[msg("Unauthorized to cancel this auction")]
+    UnauthorizedCancellation,
+
+    #[msg("Cannot cancel auction with existing bids")]
+    CannotCancelWithBids,
+
+    #[msg("Collateral must be 0 or at least the minimum bid")]
+    InvalidCollateral,
+
+    #[msg("Deposit does not meet the auction's collateral requirement")]
+    InvalidDeposit,
+
+    #[msg("Winning bid exceeds the winner's deposit")]
+    InsufficientDeposit,
+
+    #[msg("Bid does not belong to this auction")]
+    BidAuctionMismatch,
+
+    #[msg("Auction has not been finalized or cancelled")]
+    AuctionNotSettled,
+
+    #[msg("Unauthorized to claim these funds")]
+    UnauthorizedClaim,
+
+    #[msg("Winning bid deposit is released through claim_proceeds")]
+    WinnerCannotRefund,
+
+    #[msg("Funds have already been settled")]
+    AlreadySettled,
+
+    #[msg("Bid is not the winning bid")]
+    NotWinningBid,
+
+    #[msg("Vault holds insufficient funds")]
+    InsufficientEscrow,
+
+    #[msg("Token accounts are required for SPL-denominated auctions")]
+    MissingTokenAccount,
+
+    #[msg("Quote vault is not the auction's associated token account")]
+    InvalidQuoteVault,
+
+    #[msg("Token account is not owned by the expected wallet")]
+    InvalidTokenAccount,
+
+    #[msg("Asset mint cannot be the auction's quote mint")]
+    AssetMintIsQuoteMint,
+
+    #[msg("Asset amount must be greater than 0")]
+    InvalidAssetAmount,
+
+    #[msg("Asset vault is not the auction's associated token account")]
+    InvalidAssetVault,
+
+    #[msg("Every bid of the auction must be passed exactly once")]
+    IncompleteBidSet,
+
+    #[msg("MXE account has no cluster assigned")]
+    ClusterNotSet,
+
+    #[msg("Auction is not awaiting an MPC result")]
+    AuctionNotFinalizing,
+
+    #[msg("MPC computation failed")]
+    ComputationFailed,
+
+    #[msg("Invalid quantity")]
+    InvalidQuantity,
+
+    #[msg("Uniform-price auctions settle through claim_allocation")]
+    UseClaimAllocation,
+
+    #[msg("Reveal end time must be after the auction end time")]
+    InvalidRevealEndTime,
+
+    #[msg("Auction is not a commit-reveal auction")]
+    NotCommitReveal,
+
+    #[msg("Commit-reveal auctions are finalized through finalize_reveals")]
+    UseFinalizeReveals,
+
+    #[msg("Reveal phase has ended")]
+    RevealPhaseEnded,
+
+    #[msg("Reveal phase has not ended yet")]
+    RevealPhaseNotEnded,
+
+    #[msg("Bid has already been revealed")]
+    AlreadyRevealed,
+
+    #[msg("Revealed amount and salt do not match the commitment")]
+    InvalidReveal,
+
+    #[msg("Deposit was forfeited because the bid was not revealed")]
+    DepositForfeited,
+
+    #[msg("Soft close settings must be non-negative and complete")]
+    InvalidSoftClose,
+
+    #[msg("Auction has not started yet")]
+    AuctionNotStarted,
+
+    #[msg("Auction can only be edited before it starts")]
+    AuctionAlreadyStarted,
+
+    #[msg("Unauthorized to update this auction")]
+    UnauthorizedUpdate,
+
+    #[msg("Creator auction counter overflow")]
+    AuctionCountOverflow,
+
+    #[msg("Bid revision counter overflow")]
+    RevisionOverflow,
+
+    #[msg("This auction does not allow bid withdrawals")]
+    WithdrawalsDisabled,
+
+    #[msg("Settlement is not complete yet")]
+    SettlementPending,
+
+    #[msg("Unauthorized to administer the protocol config")]
+    UnauthorizedAdmin,
+
+    #[msg("Protocol fee cannot exceed 10000 basis points")]
+    InvalidFeeBps,
+
+    #[msg("Config bounds are inconsistent or exceed account capacity")]
+    InvalidConfig,
+
+    #[msg("Auction duration is outside the configured bounds")]
+    InvalidDuration,
+
+    #[msg("Fee recipient does not match the protocol config")]
+    InvalidFeeRecipient,
+
+    #[msg("Too many revenue split recipients")]
+    TooManyRevenueShares,
+
+    #[msg("Revenue shares must be non-zero and sum to 10000 basis points")]
+    InvalidRevenueShares,
+
+    #[msg("Revenue split recipient accounts do not match the auction")]
+    InvalidRevenueRecipient,
+
+    #[msg("Metadata account does not belong to the asset mint")]
+    InvalidAssetMetadata,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Auction is frozen")]
+    AuctionFrozen,
+
+    #[msg("Unauthorized to pause the protocol or freeze auctions")]
+    UnauthorizedPauser,
+
+    #[msg("Bidder is not on the auction's allowlist")]
+    NotAllowlisted,
+
+    #[msg("Token gate minimum amount must be greater than 0")]
+    InvalidBidGate,
+
+    #[msg("Bidder does not hold the token or collection this auction is gated on")]
+    BidGateNotMet,
+
+    #[msg("Nonce registry account is required for encrypted bids")]
+    MissingNonceRegistry,
+
+    #[msg("Nonce has already been used with this x25519 key")]
+    NonceReused,
+
+    #[msg("x25519 key is registered to another bidder in this auction")]
+    KeyRegisteredToOtherBidder,
+
+    #[msg("A registered MXE cluster is required for encrypted auctions")]
+    MissingCluster,
+
+    #[msg("MXE cluster has been deprecated")]
+    ClusterInactive,
+
+    #[msg("Finalization deadline has not passed yet")]
+    FinalizationDeadlineNotReached,
+
+    #[msg("Keeper account does not match the auction's keeper")]
+    InvalidKeeper,
+
+    #[msg("Payment window requires fixed collateral and a single-winner auction")]
+    InvalidPaymentWindow,
+
+    #[msg("Auction is not awaiting the winner's payment")]
+    NotAwaitingPayment,
+
+    #[msg("Payment deadline has passed")]
+    PaymentDeadlinePassed,
+
+    #[msg("Payment deadline has not passed yet")]
+    PaymentDeadlineNotReached,
+
+    #[msg("Runner-up bid does not match the MPC result")]
+    InvalidRunnerUp,
+
+    #[msg("Commit-reveal auctions do not support an encrypted reserve")]
+    ReserveNotSupported,
+
+    #[msg("MPC result is inconsistent with the reported outcome")]
+    InvalidComputationResult,
+
+    #[msg("Maximum bids must be between 1 and MAX_BIDS_PER_AUCTION")]
+    InvalidMaxBids,
+
+    #[msg("Auction has reached its maximum number of bids")]
+    TooManyBids,
 }
 
-// ============================================================================
-// Errors
-// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+    use anchor_lang::solana_program::message::Message;
+    use anchor_lang::InstructionData;
 
-#[error_code]
-pub enum AuctionError {
-    #[msg("Auction end time must be in the future")]
-    InvalidEndTime,
+    const AUCTION: Pubkey = Pubkey::new_from_array([1; 32]);
+    const BIDDER: Pubkey = Pubkey::new_from_array([2; 32]);
+    const OTHER_BIDDER: Pubkey = Pubkey::new_from_array([3; 32]);
+    const X25519_KEY: [u8; 32] = [4; 32];
 
-    #[msg("Minimum bid must be greater than 0")]
-    InvalidMinBid,
+    /// An `AccountInfo` owned by this program holding `account`
+    ///
+    /// The backing storage is leaked so the info lives for `'static`, like
+    /// the `'info` accounts handlers receive.
+    fn account_info<T: AccountSerialize>(key: Pubkey, account: &T) -> AccountInfo<'static> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            true,
+            Box::leak(Box::new(1)),
+            data.leak(),
+            &crate::ID,
+            false,
+            0,
+        )
+    }
 
-    #[msg("Item name too long (max 64 characters)")]
-    ItemNameTooLong,
+    /// The auction account at `AUCTION` holding `state`
+    fn auction_account(state: &Auction) -> Account<'static, Auction> {
+        Account::try_from(Box::leak(Box::new(account_info(AUCTION, state)))).unwrap()
+    }
 
-    #[msg("Description too long (max 256 characters)")]
-    DescriptionTooLong,
+    fn auction(kind: AuctionKind) -> Auction {
+        Auction {
+            creator: Pubkey::default(),
+            index: 0,
+            item_name: String::new(),
+            description: String::new(),
+            min_bid: 100,
+            end_time: 1_000,
+            created_at: 0,
+            start_time: 0,
+            status: AuctionStatus::Active,
+            bid_count: 0,
+            max_bids: MAX_BIDS_PER_AUCTION,
+            arcium_mxe_pubkey: [0; 32],
+            cluster_id: None,
+            collateral: 0,
+            quote_mint: None,
+            kind,
+            encrypted_reserve: None,
+            quantity: 1,
+            units_sold: 0,
+            reveal_end_time: None,
+            forfeited_deposits: 0,
+            forfeits_claimed: false,
+            extension_window: 0,
+            extension_duration: 0,
+            max_extension: 0,
+            extended_by: 0,
+            allow_withdrawals: false,
+            fee_bps: 0,
+            keeper_fee_bps: 0,
+            keeper_bounty: 0,
+            keeper: None,
+            payment_window: 0,
+            default_policy: DefaultPolicy::FailAuction,
+            revenue_shares: Vec::new(),
+            frozen: false,
+            allowlist_root: None,
+            bid_gate: None,
+            closed_bid_count: 0,
+            winner: None,
+            winning_bid: None,
+            clearing_price: None,
+            mpc_computation_id: None,
+            computation_account: None,
+            computation_bids: Vec::new(),
+            finalization_deadline: None,
+            payment_deadline: None,
+            runner_up: None,
+            runner_up_bid: None,
+            finalized_at: None,
+            proceeds_claimed: false,
+            asset_mint: None,
+            asset_amount: 0,
+            asset_released: false,
+            bump: 0,
+        }
+    }
 
-    #[msg("Auction is not active")]
-    AuctionNotActive,
+    fn bid(auction: Pubkey, bidder: Pubkey) -> Bid {
+        Bid {
+            auction,
+            bidder,
+            encrypted_data: vec![0; 32],
+            x25519_pubkey: [0; 32],
+            nonce: [0; 16],
+            timestamp: 0,
+            deposit: 100,
+            settled: false,
+            revealed_amount: None,
+            revision: 0,
+            bump: 0,
+        }
+    }
 
-    #[msg("Auction has already ended")]
-    AuctionEnded,
+    #[test]
+    fn load_bids_accepts_each_bid_once() {
+        let mut state = auction(AuctionKind::FirstPrice);
+        state.bid_count = 2;
+        let auction = auction_account(&state);
 
-    #[msg("Auction has not ended yet")]
-    AuctionNotEnded,
+        let first = account_info(Pubkey::new_unique(), &bid(AUCTION, BIDDER));
+        let second = account_info(Pubkey::new_unique(), &bid(AUCTION, OTHER_BIDDER));
+        let bids = load_bids(&auction, &[first, second]).unwrap();
 
-    #[msg("Invalid encrypted data length")]
-    InvalidEncryptedData,
+        let bidders: Vec<Pubkey> = bids.iter().map(|bid| bid.bidder).collect();
+        assert_eq!(bidders, [BIDDER, OTHER_BIDDER]);
+    }
 
-    #[msg("Invalid x25519 public key")]
-    InvalidPublicKey,
+    #[test]
+    fn request_finalization_with_a_full_bid_set_fits_in_one_transaction() {
+        let authority = Pubkey::new_unique();
+        let computation_offset = u64::MAX;
+        let mut accounts = crate::accounts::RequestFinalization {
+            auction: AUCTION,
+            config: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            authority,
+            sign_pda_account: derive_sign_pda!(),
+            mxe_account: derive_mxe_pda!(),
+            mempool_account: derive_mempool_pda!(),
+            executing_pool: derive_execpool_pda!(),
+            computation_account: derive_comp_pda!(computation_offset),
+            comp_def_account: derive_comp_def_pda!(COMP_DEF_OFFSET_FINALIZE_AUCTION),
+            cluster_account: Pubkey::new_unique(),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: ARCIUM_PROG_ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(
+            (0..MAX_BIDS_PER_AUCTION)
+                .map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false)),
+        );
+        let instruction = Instruction {
+            program_id: crate::ID,
+            accounts,
+            data: crate::instruction::RequestFinalization { computation_offset }.data(),
+        };
 
-    #[msg("Invalid encryption nonce")]
-    InvalidNonce,
+        // The signature count and the authority's signature precede the message
+        let message = Message::new(&[instruction], Some(&authority));
+        let len = 1 + 64 + message.serialize().len();
+        assert!(len <= 1_232, "transaction is {} bytes", len);
+    }
 
-    #[msg("Unauthorized to finalize this auction")]
-    UnauthorizedFinalizer,
+    #[test]
+    fn bid_ciphertext_offset_points_at_the_sealed_bid() {
+        let mut sealed = bid(AUCTION, BIDDER);
+        sealed.encrypted_data = vec![7; SEALED_BID_LEN];
+        let info = account_info(Pubkey::new_unique(), &sealed);
 
-    #[msg("Winning bid is below minimum bid")]
-    WinningBidTooLow,
+        let data = info.try_borrow_data().unwrap();
+        let start = BID_CIPHERTEXT_OFFSET as usize;
+        assert_eq!(data[start..start + SEALED_BID_LEN], [7; SEALED_BID_LEN]);
+    }
 
-    #[msg("Unauthorized to cancel this auction")]
-    UnauthorizedCancellation,
+    #[test]
+    fn finalize_auction_args_fill_every_slot_in_circuit_order() {
+        let mut state = auction(AuctionKind::FirstPrice);
+        state.bid_count = 1;
+        let auction = auction_account(&state);
+        let key = Pubkey::new_unique();
+        let mut sealed = bid(AUCTION, BIDDER);
+        sealed.nonce = 5u128.to_le_bytes();
 
-    #[msg("Cannot cancel auction with existing bids")]
-    CannotCancelWithBids,
-}
\ No newline at end of file
+        let args = finalize_auction_args(&auction, &[(key, sealed)]);
+
+        // Three arguments for the sealed bid and five for each padded slot,
+        // two per slot's terms, five auction terms and the three of the reserve
+        let slots = MAX_BIDS_PER_AUCTION as usize;
+        let terms = 3 + (slots - 1) * 5;
+        assert_eq!(args.len(), terms + slots * 2 + 5 + 3);
+        assert!(matches!(args[1], Argument::PlaintextU128(5)));
+        assert!(matches!(
+            args[2],
+            Argument::Account(account, BID_CIPHERTEXT_OFFSET, 96) if account == key
+        ));
+        assert!(matches!(args[3], Argument::ArcisPubkey(PADDING_X25519_KEY)));
+
+        let tag = bid_tag(&AUCTION, &BIDDER);
+        assert!(matches!(args[terms], Argument::PlaintextU128(t) if t == tag));
+        assert!(matches!(args[terms + 1], Argument::PlaintextU64(100)));
+        assert!(matches!(args[terms + 2], Argument::PlaintextU128(0)));
+        assert!(matches!(args[terms + slots * 2], Argument::PlaintextU8(1)));
+        assert!(matches!(args[terms + slots * 2 + 3], Argument::PlaintextBool(false)));
+        assert!(matches!(args[terms + slots * 2 + 4], Argument::PlaintextBool(false)));
+    }
+
+    #[test]
+    fn finalize_auction_args_lift_the_payment_cap_of_partially_funded_bids() {
+        let mut state = auction(AuctionKind::FirstPrice);
+        state.bid_count = 1;
+        state.payment_window = 3_600;
+        let auction = auction_account(&state);
+
+        let args = finalize_auction_args(&auction, &[(Pubkey::new_unique(), bid(AUCTION, BIDDER))]);
+
+        let terms = 3 + (MAX_BIDS_PER_AUCTION as usize - 1) * 5;
+        assert!(matches!(args[terms + 1], Argument::PlaintextU64(u64::MAX)));
+    }
+
+    #[test]
+    fn bid_tag_binds_auction_and_bidder() {
+        let tag = bid_tag(&AUCTION, &BIDDER);
+        assert_ne!(tag, bid_tag(&AUCTION, &OTHER_BIDDER));
+        assert_ne!(tag, bid_tag(&BIDDER, &AUCTION));
+    }
+
+    #[test]
+    fn bid_tag_matches_the_client_derivation() {
+        // Same vector as `computeBidTag` in the web client
+        let auction = Pubkey::new_from_array([1; 32]);
+        let bidder = Pubkey::new_from_array([2; 32]);
+        assert_eq!(
+            bid_tag(&auction, &bidder),
+            149_076_381_209_681_469_455_367_837_111_880_194_296
+        );
+    }
+
+    #[test]
+    fn load_bids_rejects_a_bid_passed_twice() {
+        let mut state = auction(AuctionKind::FirstPrice);
+        state.bid_count = 2;
+        let auction = auction_account(&state);
+
+        // The count matches, but the second bid is left out
+        let first = account_info(Pubkey::new_unique(), &bid(AUCTION, BIDDER));
+        assert_eq!(
+            load_bids(&auction, &[first.clone(), first]).err(),
+            Some(AuctionError::IncompleteBidSet.into())
+        );
+    }
+
+    #[test]
+    fn load_bids_rejects_missing_and_foreign_bids() {
+        let mut state = auction(AuctionKind::FirstPrice);
+        state.bid_count = 2;
+        let auction = auction_account(&state);
+
+        let first = account_info(Pubkey::new_unique(), &bid(AUCTION, BIDDER));
+        assert_eq!(
+            load_bids(&auction, std::slice::from_ref(&first)).err(),
+            Some(AuctionError::IncompleteBidSet.into())
+        );
+
+        let foreign = account_info(Pubkey::new_unique(), &bid(Pubkey::new_unique(), OTHER_BIDDER));
+        assert_eq!(
+            load_bids(&auction, &[first, foreign]).err(),
+            Some(AuctionError::BidAuctionMismatch.into())
+        );
+    }
+
+    #[test]
+    fn clearing_price_first_price_pays_own_bid() {
+        let auction = auction(AuctionKind::FirstPrice);
+        assert_eq!(auction.clearing_price(500, Some(300)), 500);
+        assert_eq!(auction.clearing_price(500, None), 500);
+    }
+
+    #[test]
+    fn clearing_price_second_price_pays_runner_up_floored_at_min_bid() {
+        let auction = auction(AuctionKind::SecondPrice);
+        assert_eq!(auction.clearing_price(500, Some(300)), 300);
+        assert_eq!(auction.clearing_price(500, None), 100);
+        assert_eq!(auction.clearing_price(500, Some(40)), 100);
+    }
+
+    #[test]
+    fn clearing_price_uniform_and_commit_reveal_use_reported_price() {
+        for kind in [AuctionKind::UniformPrice, AuctionKind::CommitReveal] {
+            let auction = auction(kind);
+            assert_eq!(auction.clearing_price(250, Some(200)), 250);
+            assert_eq!(auction.clearing_price(250, None), 250);
+        }
+    }
+
+    fn uniform_result(price: u64, allocations: &[(Pubkey, u64)]) -> ComputationResult {
+        ComputationResult {
+            winner: None,
+            winning_bid: price,
+            second_bid: None,
+            runner_up: None,
+            reserve_met: true,
+            allocations: allocations
+                .iter()
+                .map(|&(bidder, quantity)| UnitAllocation { bidder, quantity })
+                .collect(),
+            rejected_bids: 0,
+        }
+    }
+
+    fn computation_bid(bidder: Pubkey, deposit: u64) -> ComputationBid {
+        ComputationBid {
+            bidder,
+            deposit,
+            allocated_quantity: 0,
+        }
+    }
+
+    /// An auction of `kind` queued with bids of `BIDDER` (deposit 300) and
+    /// `OTHER_BIDDER` (deposit 100), in that slot order
+    fn queued_auction(kind: AuctionKind) -> Auction {
+        let mut state = auction(kind);
+        state.computation_bids = vec![
+            computation_bid(BIDDER, 300),
+            computation_bid(OTHER_BIDDER, 100),
+        ];
+        state
+    }
+
+    #[test]
+    fn record_allocations_writes_units_to_queued_bids() {
+        let mut auction = queued_auction(AuctionKind::UniformPrice);
+        auction.quantity = 4;
+
+        let result = uniform_result(100, &[(BIDDER, 2), (OTHER_BIDDER, 1)]);
+        record_allocations(&mut auction, &result).unwrap();
+
+        assert_eq!(auction.allocated_quantity(&BIDDER), 2);
+        assert_eq!(auction.allocated_quantity(&OTHER_BIDDER), 1);
+        assert_eq!(auction.units_sold, 3);
+        assert_eq!(auction.clearing_price, Some(100));
+    }
+
+    #[test]
+    fn record_allocations_rejects_invalid_results() {
+        let mut auction = queued_auction(AuctionKind::UniformPrice);
+        auction.quantity = 2;
+
+        let cases = [
+            // Price below min_bid
+            (uniform_result(60, &[(BIDDER, 1)]), AuctionError::WinningBidTooLow),
+            // Two units at 100 exceed the 100 deposit
+            (
+                uniform_result(100, &[(BIDDER, 1), (OTHER_BIDDER, 2)]),
+                AuctionError::InsufficientDeposit,
+            ),
+            // Three units allocated out of two
+            (
+                uniform_result(100, &[(BIDDER, 2), (OTHER_BIDDER, 1)]),
+                AuctionError::InvalidQuantity,
+            ),
+            (uniform_result(100, &[(BIDDER, 0)]), AuctionError::InvalidQuantity),
+            // The same bidder allocated twice
+            (
+                uniform_result(100, &[(BIDDER, 1), (BIDDER, 1)]),
+                AuctionError::InvalidQuantity,
+            ),
+            // Allocation to a bidder with no queued bid
+            (
+                uniform_result(100, &[(Pubkey::new_unique(), 1)]),
+                AuctionError::NotWinningBid,
+            ),
+        ];
+        for (result, error) in cases {
+            assert_eq!(
+                record_allocations(&mut auction, &result).err(),
+                Some(error.into())
+            );
+        }
+        assert_eq!(auction.units_sold, 0);
+        assert_eq!(auction.allocated_quantity(&BIDDER), 0);
+    }
+
+    /// A revealed outcome with no eligible bid
+    fn empty_outcome() -> FinalizeAuctionOutputStruct0 {
+        FinalizeAuctionOutputStruct0 {
+            field_0: true,
+            field_1: false,
+            field_2: 0,
+            field_3: 0,
+            field_4: false,
+            field_5: 0,
+            field_6: 0,
+            field_7: 0,
+            field_8: [0; MAX_BIDS_PER_AUCTION as usize],
+            field_9: 0,
+            field_10: false,
+        }
+    }
+
+    #[test]
+    fn from_outcome_maps_slots_to_queued_bidders() {
+        let auction = queued_auction(AuctionKind::SecondPrice);
+        let mut outcome = empty_outcome();
+        outcome.field_1 = true;
+        outcome.field_2 = 1;
+        outcome.field_3 = 500;
+        outcome.field_4 = true;
+        outcome.field_5 = 0;
+        outcome.field_6 = 300;
+        outcome.field_9 = 2;
+        outcome.field_10 = true;
+
+        let result = ComputationResult::from_outcome(&auction, &outcome).unwrap();
+
+        assert_eq!(result.winner, Some(OTHER_BIDDER));
+        assert_eq!(result.winning_bid, 500);
+        assert_eq!(result.runner_up, Some(BIDDER));
+        assert_eq!(result.second_bid, Some(300));
+        assert!(result.reserve_met);
+        assert!(result.allocations.is_empty());
+        assert_eq!(result.rejected_bids, 2);
+    }
+
+    #[test]
+    fn from_outcome_without_a_winner_is_empty() {
+        let auction = queued_auction(AuctionKind::FirstPrice);
+        let mut outcome = empty_outcome();
+        outcome.field_0 = false;
+
+        let result = ComputationResult::from_outcome(&auction, &outcome).unwrap();
+
+        assert_eq!(result.winner, None);
+        assert_eq!(result.winning_bid, 0);
+        assert_eq!(result.runner_up, None);
+        assert_eq!(result.second_bid, None);
+        assert!(!result.reserve_met);
+    }
+
+    #[test]
+    fn from_outcome_keeps_a_reserve_second_price_without_a_runner_up() {
+        let mut auction = queued_auction(AuctionKind::SecondPrice);
+        auction.encrypted_reserve = Some(EncryptedValue {
+            ciphertext: [0; 32],
+            x25519_pubkey: [0; 32],
+            nonce: [0; 16],
+        });
+        let mut outcome = empty_outcome();
+        outcome.field_1 = true;
+        outcome.field_3 = 500;
+        outcome.field_4 = true;
+        outcome.field_6 = 250;
+
+        let result = ComputationResult::from_outcome(&auction, &outcome).unwrap();
+
+        assert_eq!(result.winner, Some(BIDDER));
+        assert_eq!(result.second_bid, Some(250));
+        assert_eq!(result.runner_up, None);
+        assert_eq!(auction.clearing_price(result.winning_bid, result.second_bid), 250);
+    }
+
+    #[test]
+    fn from_outcome_lists_uniform_allocations_at_the_clearing_price() {
+        let auction = queued_auction(AuctionKind::UniformPrice);
+        let mut outcome = empty_outcome();
+        outcome.field_1 = true;
+        outcome.field_3 = 400;
+        outcome.field_7 = 150;
+        outcome.field_8[1] = 3;
+
+        let result = ComputationResult::from_outcome(&auction, &outcome).unwrap();
+
+        assert_eq!(result.winner, None);
+        assert_eq!(result.winning_bid, 150);
+        let allocations: Vec<(Pubkey, u64)> = result
+            .allocations
+            .iter()
+            .map(|allocation| (allocation.bidder, allocation.quantity))
+            .collect();
+        assert_eq!(allocations, [(OTHER_BIDDER, 3)]);
+    }
+
+    #[test]
+    fn from_outcome_rejects_slots_without_a_queued_bid() {
+        let single = queued_auction(AuctionKind::FirstPrice);
+        let mut outcome = empty_outcome();
+        outcome.field_1 = true;
+        outcome.field_2 = 2;
+        assert_eq!(
+            ComputationResult::from_outcome(&single, &outcome).err(),
+            Some(AuctionError::InvalidComputationResult.into())
+        );
+
+        let uniform = queued_auction(AuctionKind::UniformPrice);
+        let mut outcome = empty_outcome();
+        outcome.field_8[5] = 1;
+        assert_eq!(
+            ComputationResult::from_outcome(&uniform, &outcome).err(),
+            Some(AuctionError::InvalidComputationResult.into())
+        );
+    }
+
+    /// A bid of `deposit` opened at `revealed`, placed at `timestamp`
+    fn revealed_bid(revealed: Option<u64>, deposit: u64, timestamp: i64) -> Bid {
+        let mut bid = bid(AUCTION, Pubkey::new_unique());
+        bid.revealed_amount = revealed;
+        bid.deposit = deposit;
+        bid.timestamp = timestamp;
+        bid
+    }
+
+    #[test]
+    fn reveal_winner_is_highest_covered_bid_with_earliest_tie() {
+        let bids = [
+            revealed_bid(Some(300), 300, 5),
+            revealed_bid(Some(500), 500, 7),
+            revealed_bid(Some(500), 500, 6),
+            revealed_bid(Some(400), 500, 1),
+        ];
+
+        let (winner, forfeited) = select_reveal_winner(&bids, 100).unwrap();
+        assert_eq!(winner.map(|bid| bid.bidder), Some(bids[2].bidder));
+        assert_eq!(forfeited, 0);
+    }
+
+    #[test]
+    fn reveal_winner_skips_invalid_bids_and_forfeits_unrevealed() {
+        let bids = [
+            // Below min_bid
+            revealed_bid(Some(50), 500, 1),
+            // Not covered by the deposit
+            revealed_bid(Some(900), 500, 2),
+            revealed_bid(None, 200, 3),
+            revealed_bid(None, 300, 4),
+            revealed_bid(Some(150), 150, 5),
+        ];
+
+        let (winner, forfeited) = select_reveal_winner(&bids, 100).unwrap();
+        assert_eq!(winner.map(|bid| bid.bidder), Some(bids[4].bidder));
+        assert_eq!(forfeited, 500);
+
+        let (winner, forfeited) = select_reveal_winner(&bids[..4], 100).unwrap();
+        assert!(winner.is_none());
+        assert_eq!(forfeited, 500);
+    }
+
+    #[test]
+    fn soft_close_ignores_bids_outside_the_window() {
+        let mut auction = auction(AuctionKind::FirstPrice);
+        auction.extension_window = 60;
+        auction.extension_duration = 120;
+        auction.max_extension = 300;
+
+        assert_eq!(auction.apply_soft_close(939), None);
+        assert_eq!(auction.end_time, 1_000);
+        assert_eq!(auction.extended_by, 0);
+    }
+
+    #[test]
+    fn soft_close_stops_at_max_extension() {
+        let mut auction = auction(AuctionKind::FirstPrice);
+        auction.extension_window = 60;
+        auction.extension_duration = 120;
+        auction.max_extension = 300;
+
+        assert_eq!(auction.apply_soft_close(950), Some(1_000));
+        assert_eq!(auction.apply_soft_close(1_100), Some(1_120));
+        assert_eq!((auction.end_time, auction.extended_by), (1_240, 240));
+
+        // Only 60 seconds of the cap remain
+        assert_eq!(auction.apply_soft_close(1_200), Some(1_240));
+        assert_eq!((auction.end_time, auction.extended_by), (1_300, 300));
+
+        assert_eq!(auction.apply_soft_close(1_290), None);
+        assert_eq!((auction.end_time, auction.extended_by), (1_300, 300));
+    }
+
+    #[test]
+    fn soft_close_moves_reveal_end_time_along() {
+        let mut auction = auction(AuctionKind::CommitReveal);
+        auction.reveal_end_time = Some(2_000);
+        auction.extension_window = 60;
+        auction.extension_duration = 120;
+        auction.max_extension = 300;
+
+        assert_eq!(auction.apply_soft_close(990), Some(1_000));
+        assert_eq!(auction.reveal_end_time, Some(2_120));
+    }
+
+    #[test]
+    fn protocol_fee_rounds_down_in_favour_of_the_creator() {
+        let mut auction = auction(AuctionKind::FirstPrice);
+        assert_eq!(auction.protocol_fee(1_000_000), 0);
+
+        auction.fee_bps = 250;
+        assert_eq!(auction.protocol_fee(1_000_000), 25_000);
+        assert_eq!(auction.protocol_fee(39), 0);
+        assert_eq!(auction.protocol_fee(41), 1);
+        assert_eq!(auction.protocol_fee(u64::MAX), u64::MAX / 40);
+
+        auction.fee_bps = BPS_DENOMINATOR;
+        assert_eq!(auction.protocol_fee(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn keeper_fee_splits_the_protocol_fee_only_with_a_keeper() {
+        let mut auction = auction(AuctionKind::FirstPrice);
+        auction.fee_bps = 250;
+        auction.keeper_fee_bps = 2_000;
+        let fee = auction.protocol_fee(1_000_003);
+        assert_eq!(fee, 25_000);
+
+        // Finalized by the creator: the fee recipient keeps everything
+        assert_eq!(auction.keeper_fee(fee), 0);
+
+        auction.keeper = Some(Pubkey::new_unique());
+        assert_eq!(auction.keeper_fee(fee), 5_000);
+        assert_eq!(auction.keeper_fee(9), 1);
+        assert_eq!(auction.keeper_fee(4), 0);
+        assert!(auction.keeper_fee(fee) <= fee);
+
+        auction.keeper_fee_bps = BPS_DENOMINATOR;
+        assert_eq!(auction.keeper_fee(fee), fee);
+    }
+
+    #[test]
+    fn config_validate_rejects_out_of_range_settings() {
+        let config = Config {
+            admin: Pubkey::default(),
+            pause_authority: Pubkey::default(),
+            paused: false,
+            fee_bps: 250,
+            fee_recipient: Pubkey::default(),
+            keeper_fee_bps: 1_000,
+            max_item_name_len: Config::ITEM_NAME_CAPACITY,
+            max_description_len: Config::DESCRIPTION_CAPACITY,
+            min_duration: 60,
+            max_duration: 3_600,
+            finalization_timeout: 86_400,
+            bump: 0,
+        };
+        assert!(config.validate().is_ok());
+        assert!(config.is_valid_duration(60) && config.is_valid_duration(3_600));
+        assert!(!config.is_valid_duration(59) && !config.is_valid_duration(3_601));
+
+        let with = |update: fn(&mut Config)| {
+            let mut config = config.clone();
+            update(&mut config);
+            config
+        };
+        let cases = [
+            (
+                with(|c| c.fee_bps = BPS_DENOMINATOR + 1),
+                AuctionError::InvalidFeeBps,
+            ),
+            (
+                with(|c| c.keeper_fee_bps = BPS_DENOMINATOR + 1),
+                AuctionError::InvalidFeeBps,
+            ),
+            (with(|c| c.max_item_name_len = 0), AuctionError::InvalidConfig),
+            (with(|c| c.max_item_name_len += 1), AuctionError::InvalidConfig),
+            (with(|c| c.max_description_len += 1), AuctionError::InvalidConfig),
+            (with(|c| c.min_duration = 0), AuctionError::InvalidConfig),
+            (with(|c| c.max_duration = 59), AuctionError::InvalidConfig),
+            (with(|c| c.finalization_timeout = 0), AuctionError::InvalidConfig),
+        ];
+        for (config, err) in cases {
+            assert_eq!(config.validate().err(), Some(err.into()));
+        }
+    }
+
+    fn share(bps: u16) -> RevenueShare {
+        RevenueShare {
+            recipient: Pubkey::new_unique(),
+            bps,
+        }
+    }
+
+    #[test]
+    fn revenue_payouts_round_down_and_give_dust_to_the_last_recipient() {
+        let shares = [share(3_333), share(3_333), share(3_334)];
+        assert_eq!(revenue_payouts(100, &shares), [33, 33, 34]);
+        assert_eq!(revenue_payouts(10, &shares), [3, 3, 4]);
+        assert_eq!(revenue_payouts(0, &shares), [0, 0, 0]);
+
+        let payouts = revenue_payouts(u64::MAX, &shares);
+        assert_eq!(payouts.iter().map(|&p| p as u128).sum::<u128>(), u64::MAX as u128);
+
+        assert_eq!(revenue_payouts(999, &[share(BPS_DENOMINATOR)]), [999]);
+    }
+
+    #[test]
+    fn validate_revenue_shares_requires_a_full_split() {
+        assert!(validate_revenue_shares(&[]).is_ok());
+        assert!(validate_revenue_shares(&[share(2_500), share(7_500)]).is_ok());
+
+        let too_many: Vec<_> = (0..=MAX_REVENUE_SHARES).map(|_| share(1)).collect();
+        let cases = [
+            (too_many, AuctionError::TooManyRevenueShares),
+            (vec![share(2_500), share(7_499)], AuctionError::InvalidRevenueShares),
+            (vec![share(0), share(BPS_DENOMINATOR)], AuctionError::InvalidRevenueShares),
+        ];
+        for (shares, err) in cases {
+            assert_eq!(validate_revenue_shares(&shares).err(), Some(err.into()));
+        }
+    }
+
+    fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[&[0x01], left, right]).to_bytes()
+    }
+
+    /// Four-wallet allowlist: the wallets, the four leaves and the root
+    fn allowlist() -> ([Pubkey; 4], [[u8; 32]; 4], [u8; 32]) {
+        let wallets = [10, 11, 12, 13].map(|byte| Pubkey::new_from_array([byte; 32]));
+        let leaves = wallets.map(|wallet| allowlist_leaf(&wallet));
+        let root = parent(&parent(&leaves[0], &leaves[1]), &parent(&leaves[2], &leaves[3]));
+        (wallets, leaves, root)
+    }
+
+    #[test]
+    fn allowlist_proof_accepts_every_member() {
+        let (wallets, leaves, root) = allowlist();
+        let proofs = [
+            [leaves[1], parent(&leaves[2], &leaves[3])],
+            [leaves[0], parent(&leaves[2], &leaves[3])],
+            [leaves[3], parent(&leaves[0], &leaves[1])],
+            [leaves[2], parent(&leaves[0], &leaves[1])],
+        ];
+
+        for (wallet, proof) in wallets.iter().zip(proofs) {
+            assert!(verify_allowlist_proof(&root, &allowlist_leaf(wallet), &proof));
+        }
+    }
+
+    #[test]
+    fn allowlist_proof_rejects_wrong_leaf() {
+        let (_, leaves, root) = allowlist();
+        let proof = [leaves[1], parent(&leaves[2], &leaves[3])];
+        let outsider = Pubkey::new_from_array([99; 32]);
+
+        assert!(!verify_allowlist_proof(&root, &allowlist_leaf(&outsider), &proof));
+        assert!(!verify_allowlist_proof(&root, &leaves[0], &proof[..1]));
+    }
+
+    #[test]
+    fn allowlist_proof_rejects_inner_node_as_leaf() {
+        let (_, leaves, root) = allowlist();
+        let inner = parent(&leaves[0], &leaves[1]);
+        let sibling = parent(&leaves[2], &leaves[3]);
+
+        // The raw inner node folds to the root with the shortened proof...
+        assert!(verify_allowlist_proof(&root, &inner, &[sibling]));
+
+        // ...but a wallet whose key equals it still hashes to a leaf under
+        // the leaf prefix, so it cannot pass as a member
+        let wallet = Pubkey::new_from_array(inner);
+        assert!(!verify_allowlist_proof(&root, &allowlist_leaf(&wallet), &[sibling]));
+    }
+
+    fn nonce(value: u128) -> [u8; 16] {
+        value.to_le_bytes()
+    }
+
+    fn empty_registry() -> NonceRegistry {
+        NonceRegistry {
+            auction: Pubkey::default(),
+            x25519_pubkey: [0; 32],
+            bidder: Pubkey::default(),
+            last_nonce: [0; 16],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn nonce_registry_binds_key_on_first_use() {
+        let mut registry = empty_registry();
+        registry
+            .record(AUCTION, BIDDER, X25519_KEY, nonce(0), 255)
+            .unwrap();
+
+        assert_eq!(registry.auction, AUCTION);
+        assert_eq!(registry.bidder, BIDDER);
+        assert_eq!(registry.x25519_pubkey, X25519_KEY);
+        assert_eq!(registry.last_nonce, nonce(0));
+        assert_eq!(registry.bump, 255);
+    }
+
+    #[test]
+    fn nonce_registry_rejects_replayed_nonce() {
+        let mut registry = empty_registry();
+        registry
+            .record(AUCTION, BIDDER, X25519_KEY, nonce(7), 255)
+            .unwrap();
+
+        for replay in [nonce(7), nonce(6)] {
+            assert_eq!(
+                registry.record(AUCTION, BIDDER, X25519_KEY, replay, 255),
+                Err(AuctionError::NonceReused.into())
+            );
+        }
+        assert_eq!(registry.last_nonce, nonce(7));
+
+        registry
+            .record(AUCTION, BIDDER, X25519_KEY, nonce(8), 255)
+            .unwrap();
+        assert_eq!(registry.last_nonce, nonce(8));
+    }
+
+    #[test]
+    fn nonce_registry_rejects_key_reuse_by_another_bidder() {
+        let mut registry = empty_registry();
+        registry
+            .record(AUCTION, BIDDER, X25519_KEY, nonce(1), 255)
+            .unwrap();
+
+        assert_eq!(
+            registry.record(AUCTION, OTHER_BIDDER, X25519_KEY, nonce(2), 255),
+            Err(AuctionError::KeyRegisteredToOtherBidder.into())
+        );
+        assert_eq!(registry.bidder, BIDDER);
+        assert_eq!(registry.last_nonce, nonce(1));
+    }
+
+    /// First-price auction whose winner `BIDDER` owes 500 by time 1_000,
+    /// with `OTHER_BIDDER` second at 400
+    fn awaiting_payment(policy: DefaultPolicy) -> Auction {
+        let mut auction = auction(AuctionKind::FirstPrice);
+        auction.status = AuctionStatus::AwaitingPayment;
+        auction.winner = Some(BIDDER);
+        auction.winning_bid = Some(500);
+        auction.clearing_price = Some(500);
+        auction.payment_window = 600;
+        auction.payment_deadline = Some(1_000);
+        auction.default_policy = policy;
+        auction.runner_up = Some(OTHER_BIDDER);
+        auction.runner_up_bid = Some(400);
+        auction
+    }
+
+    #[test]
+    fn slash_split_sends_the_fee_share_to_the_protocol() {
+        let mut auction = awaiting_payment(DefaultPolicy::FailAuction);
+        assert_eq!(auction.slash_split(1_000), (1_000, 0));
+
+        auction.fee_bps = 250;
+        assert_eq!(auction.slash_split(1_000), (975, 25));
+        assert_eq!(auction.slash_split(39), (39, 0));
+        assert_eq!(auction.slash_split(0), (0, 0));
+    }
+
+    #[test]
+    fn resolve_default_fails_the_auction_without_a_runner_up_offer() {
+        let mut auction = awaiting_payment(DefaultPolicy::FailAuction);
+        assert_eq!(auction.resolve_default(None, 1_000), Ok(None));
+        assert!(auction.status == AuctionStatus::Defaulted);
+        assert_eq!((auction.winner, auction.clearing_price), (None, None));
+
+        // The policy allows it, but the runner-up was already offered the item
+        let mut auction = awaiting_payment(DefaultPolicy::OfferRunnerUp);
+        auction.runner_up = None;
+        auction.runner_up_bid = None;
+        assert_eq!(auction.resolve_default(None, 1_000), Ok(None));
+        assert!(auction.status == AuctionStatus::Defaulted);
+    }
+
+    #[test]
+    fn resolve_default_hands_the_item_to_the_runner_up_at_their_bid() {
+        // Collateral covers the runner-up's bid: settled at once
+        let mut auction = awaiting_payment(DefaultPolicy::OfferRunnerUp);
+        assert_eq!(
+            auction.resolve_default(Some((OTHER_BIDDER, 400)), 1_000),
+            Ok(Some(OTHER_BIDDER))
+        );
+        assert!(auction.status == AuctionStatus::Finalized);
+        assert_eq!(auction.winner, Some(OTHER_BIDDER));
+        assert_eq!((auction.winning_bid, auction.clearing_price), (Some(400), Some(400)));
+        assert_eq!(auction.payment_deadline, None);
+        assert_eq!((auction.runner_up, auction.runner_up_bid), (None, None));
+
+        // Partially funded: a fresh payment window, and no further runner-up
+        let mut auction = awaiting_payment(DefaultPolicy::OfferRunnerUp);
+        assert_eq!(
+            auction.resolve_default(Some((OTHER_BIDDER, 100)), 1_000),
+            Ok(Some(OTHER_BIDDER))
+        );
+        assert!(auction.status == AuctionStatus::AwaitingPayment);
+        assert_eq!(auction.payment_deadline, Some(1_600));
+        assert_eq!(auction.runner_up, None);
+
+        assert_eq!(auction.resolve_default(None, 1_600), Ok(None));
+        assert!(auction.status == AuctionStatus::Defaulted);
+    }
+
+    #[test]
+    fn resolve_default_requires_the_runner_up_bid() {
+        for runner_up_bid in [None, Some((BIDDER, 400))] {
+            let mut auction = awaiting_payment(DefaultPolicy::OfferRunnerUp);
+            assert_eq!(
+                auction.resolve_default(runner_up_bid, 1_000),
+                Err(AuctionError::InvalidRunnerUp.into())
+            );
+        }
+    }
+
+    fn creator(share: u8, verified: bool) -> Creator {
+        Creator {
+            address: Pubkey::new_unique(),
+            verified,
+            share,
+        }
+    }
+
+    #[test]
+    fn verified_creator_shares_rescale_to_a_full_split() {
+        let creators = [creator(50, true), creator(20, false), creator(30, true)];
+        let shares = verified_creator_shares(&creators);
+        assert_eq!(shares.len(), 2);
+        assert_eq!((shares[0].recipient, shares[0].bps), (creators[0].address, 6_250));
+        assert_eq!((shares[1].recipient, shares[1].bps), (creators[2].address, 3_750));
+
+        // Thirds round down, with the dust on the last creator
+        let creators = [creator(10, true), creator(10, true), creator(10, true), creator(70, false)];
+        let shares = verified_creator_shares(&creators);
+        assert_eq!(shares.iter().map(|s| s.bps).collect::<Vec<_>>(), [3_333, 3_333, 3_334]);
+        assert!(validate_revenue_shares(&shares).is_ok());
+
+        let creators = [creator(100, true), creator(0, true)];
+        assert_eq!(verified_creator_shares(&creators).len(), 1);
+        assert_eq!(verified_creator_shares(&creators)[0].bps, BPS_DENOMINATOR);
+    }
+
+    #[test]
+    fn verified_creator_shares_fall_back_to_the_auction_creator() {
+        assert!(verified_creator_shares(&[]).is_empty());
+        assert!(verified_creator_shares(&[creator(60, false), creator(40, false)]).is_empty());
+    }
+
+    /// An initialized token account at a fresh key holding `amount` of a fresh mint
+    fn token_account(owner: Pubkey, amount: u64) -> Account<'static, TokenAccount> {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::{Account as SplAccount, AccountState};
+
+        let mut data = vec![0; SplAccount::LEN];
+        SplAccount {
+            mint: Pubkey::new_unique(),
+            owner,
+            amount,
+            state: AccountState::Initialized,
+            ..SplAccount::default()
+        }
+        .pack_into_slice(&mut data);
+        let info = AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            true,
+            Box::leak(Box::new(1)),
+            data.leak(),
+            &token::ID,
+            false,
+            0,
+        );
+        Account::try_from(Box::leak(Box::new(info))).unwrap()
+    }
+
+    fn signer(key: Pubkey) -> Signer<'static> {
+        let info = AccountInfo::new(
+            Box::leak(Box::new(key)),
+            true,
+            true,
+            Box::leak(Box::new(0)),
+            Vec::new().leak(),
+            &system_program::ID,
+            false,
+            0,
+        );
+        Signer::try_from(Box::leak(Box::new(info))).unwrap()
+    }
+
+    fn token_program() -> Program<'static, Token> {
+        let info = AccountInfo::new(
+            &token::ID,
+            false,
+            false,
+            Box::leak(Box::new(0)),
+            Vec::new().leak(),
+            &system_program::ID,
+            true,
+            0,
+        );
+        Program::try_from(&*Box::leak(Box::new(info))).unwrap()
+    }
+
+    #[test]
+    fn close_auction_token_account_needs_a_creator_account_to_sweep_dust() {
+        let auction = auction_account(&auction(AuctionKind::FirstPrice));
+        let creator = signer(auction.creator);
+        let vault = token_account(AUCTION, 1);
+
+        assert_eq!(
+            close_auction_token_account(&auction, &vault, &creator, None, &token_program()).err(),
+            Some(AuctionError::MissingTokenAccount.into())
+        );
+
+        let foreign = token_account(OTHER_BIDDER, 0);
+        assert_eq!(
+            close_auction_token_account(
+                &auction,
+                &vault,
+                &creator,
+                Some(&foreign),
+                &token_program()
+            )
+            .err(),
+            Some(AuctionError::InvalidTokenAccount.into())
+        );
+    }
+
+    #[test]
+    fn close_allocation_is_limited_to_the_bidder_and_payer() {
+        let payer = Pubkey::new_unique();
+        let allocation = Allocation {
+            auction: AUCTION,
+            bidder: BIDDER,
+            bid: Pubkey::new_unique(),
+            quantity: 2,
+            price: 100,
+            claimed_at: 0,
+            payer,
+            bump: 255,
+        };
+        let close = |authority: Pubkey| {
+            let accounts = CloseAllocation {
+                allocation: Account::try_from(Box::leak(Box::new(account_info(
+                    Pubkey::new_unique(),
+                    &allocation,
+                ))))
+                .unwrap(),
+                payer: UncheckedAccount::try_from(Box::leak(Box::new(
+                    signer(payer).to_account_info(),
+                ))),
+                authority: signer(authority),
+            };
+            close_allocation(Context::new(
+                &crate::ID,
+                Box::leak(Box::new(accounts)),
+                &[],
+                CloseAllocationBumps {},
+            ))
+        };
+
+        assert!(close(BIDDER).is_ok());
+        assert!(close(payer).is_ok());
+        assert_eq!(
+            close(OTHER_BIDDER).err(),
+            Some(AuctionError::UnauthorizedClaim.into())
+        );
+    }
+}
@@ -88,7 +88,7 @@ export default function AuctionCreator({ onCreateAuction, onCancel }) {
 
       // Create auction on-chain using Anchor program
       setTxStatus('Deploying auction to Solana devnet...');
-      const result = await createAuctionWithProgram(wallet, newAuction, arciumPubkey);
+      const result = await createAuctionWithProgram(wallet, newAuction);
       
       // Add blockchain data to auction
       newAuction.onChainSignature = result.signature;
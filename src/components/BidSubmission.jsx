@@ -2,7 +2,7 @@ import React, { useState } from 'react';
 import { useWallet } from '@solana/wallet-adapter-react';
 import { encryptBid } from '../utils/arciumEncryption';
 import { validateBid } from '../utils/helpers';
-import { submitBidOnChain, getWalletBalance } from '../utils/programInstructions';
+import { submitBidOnChain, getWalletBalance, getAuctionPDA } from '../utils/programInstructions';
 
 export default function BidSubmission({ auction, onBidSubmitted, onCancel }) {
   const { connected, publicKey } = useWallet();
@@ -40,7 +40,10 @@ export default function BidSubmission({ auction, onBidSubmitted, onCancel }) {
       await new Promise(resolve => setTimeout(resolve, 500));
 
       setEncryptionStage('Encrypting bid with Rescue cipher...');
-      const encrypted = await encryptBid(BigInt(Math.floor(amount * 1e9)));
+      const encrypted = await encryptBid(BigInt(Math.floor(amount * 1e9)), {
+        auction: getAuctionPDA(auction.id),
+        bidder: publicKey,
+      });
       await new Promise(resolve => setTimeout(resolve, 800));
 
       setEncryptionStage('Submitting to Solana devnet...');
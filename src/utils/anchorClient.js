@@ -14,6 +14,22 @@ import { connection } from './solanaConnection';
 // For now, using placeholder. Update this after running `anchor deploy`
 const PROGRAM_ID = new PublicKey('AucBLdAuct1on11111111111111111111111111111');
 
+// Arcium program that runs the finalization computation
+const ARCIUM_PROGRAM_ID = new PublicKey('BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6');
+
+// Arcium fee pool and clock accounts (fixed addresses)
+const ARCIUM_FEE_POOL_ACCOUNT = new PublicKey('7MGSS4iKNM4sVib7bDZDJhVqB6EcchPwVnTKenCY1jt3');
+const ARCIUM_CLOCK_ACCOUNT = new PublicKey('FHriyvoZotYiFnbUzKFjzRSb2NiaC8RPWY7jtKuKhg65');
+
+// comp_def_offset("finalize_auction"): first 4 bytes of its SHA-256, little-endian
+const FINALIZE_AUCTION_COMP_DEF_OFFSET = 1486832694;
+
+// Registered MXE cluster new auctions encrypt to
+const DEFAULT_CLUSTER_ID = 0;
+
+// Program-wide bid cap (MAX_BIDS_PER_AUCTION), so finalization fits in one transaction
+const MAX_BIDS_PER_AUCTION = 16;
+
 /**
  * Get Anchor provider from wallet
  */
@@ -45,14 +61,43 @@ async function getProgram(wallet) {
 }
 
 /**
- * Derive auction PDA
+ * Encode an integer as little-endian bytes for PDA seeds
  */
-export function getAuctionPDA(creator, itemName) {
+function toLeBytes(value, length) {
+  return new anchor.BN(value).toArrayLike(Buffer, 'le', length);
+}
+
+/**
+ * Derive protocol config PDA
+ */
+export function getConfigPDA() {
+  const [configPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('config')],
+    PROGRAM_ID
+  );
+  return configPDA;
+}
+
+/**
+ * Derive creator profile PDA (holds the creator's auction counter)
+ */
+export function getCreatorProfilePDA(creator) {
+  const [profilePDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('creator'), creator.toBuffer()],
+    PROGRAM_ID
+  );
+  return profilePDA;
+}
+
+/**
+ * Derive auction PDA from the creator and their auction index
+ */
+export function getAuctionPDA(creator, index) {
   const [auctionPDA] = PublicKey.findProgramAddressSync(
     [
       Buffer.from('auction'),
       creator.toBuffer(),
-      Buffer.from(itemName),
+      toLeBytes(index, 8),
     ],
     PROGRAM_ID
   );
@@ -60,41 +105,174 @@ export function getAuctionPDA(creator, itemName) {
 }
 
 /**
- * Derive bid PDA
+ * Derive auction vault PDA (escrows lamport deposits)
+ */
+export function getVaultPDA(auctionPDA) {
+  const [vaultPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('vault'), auctionPDA.toBuffer()],
+    PROGRAM_ID
+  );
+  return vaultPDA;
+}
+
+/**
+ * Derive MXE cluster registry PDA
+ */
+export function getClusterPDA(clusterId) {
+  const [clusterPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('cluster'), toLeBytes(clusterId, 4)],
+    PROGRAM_ID
+  );
+  return clusterPDA;
+}
+
+/**
+ * Derive bid PDA (one bid per bidder and auction)
  */
-export function getBidPDA(auctionPDA, bidder, bidCount) {
+export function getBidPDA(auctionPDA, bidder) {
   const [bidPDA] = PublicKey.findProgramAddressSync(
     [
       Buffer.from('bid'),
       auctionPDA.toBuffer(),
       bidder.toBuffer(),
-      Buffer.from(bidCount.toString()),
     ],
     PROGRAM_ID
   );
   return bidPDA;
 }
 
+/**
+ * Derive nonce registry PDA for a bid's x25519 key
+ */
+export function getNonceRegistryPDA(auctionPDA, x25519Pubkey) {
+  const [registryPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('nonce'), auctionPDA.toBuffer(), Buffer.from(x25519Pubkey)],
+    PROGRAM_ID
+  );
+  return registryPDA;
+}
+
+/**
+ * Derive an account of this program's MXE under the Arcium program
+ */
+function getArciumPDA(seed, ...extraSeeds) {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(seed), PROGRAM_ID.toBuffer(), ...extraSeeds],
+    ARCIUM_PROGRAM_ID
+  );
+  return pda;
+}
+
+/**
+ * Derive the Arcium computation account for a finalization request
+ */
+export function getComputationPDA(computationOffset) {
+  return getArciumPDA('ComputationAccount', toLeBytes(computationOffset, 8));
+}
+
+/**
+ * Derive the program's signer PDA, which authorizes computations with Arcium
+ */
+export function getSignPDA() {
+  const [signPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('SignerAccount')],
+    PROGRAM_ID
+  );
+  return signPDA;
+}
+
+/**
+ * Read the cluster the program's MXE runs on and derive its account
+ */
+async function getMxeClusterPDA(mxeAccount) {
+  const info = await connection.getAccountInfo(mxeAccount);
+  if (!info) {
+    throw new Error('Arcium MXE account is not initialized');
+  }
+  // Discriminator, then authority: Option<Pubkey>, then cluster: Option<u32>
+  let offset = 8;
+  offset += info.data[offset] === 1 ? 33 : 1;
+  if (info.data[offset] !== 1) {
+    throw new Error('Arcium MXE has no cluster assigned');
+  }
+  const [clusterPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('Cluster'), info.data.subarray(offset + 1, offset + 5)],
+    ARCIUM_PROGRAM_ID
+  );
+  return clusterPDA;
+}
+
+/**
+ * Read the index the creator's next auction will get
+ */
+async function getNextAuctionIndex(program, creator) {
+  const profile = await program.account.creatorProfile.fetchNullable(
+    getCreatorProfilePDA(creator)
+  );
+  return profile ? profile.auctionCount : new anchor.BN(0);
+}
+
 /**
  * Create auction on-chain using deployed program
+ *
+ * Creates a lamport-denominated, first-price auction without an escrowed
+ * asset, whose bids are encrypted to the registered MXE cluster
+ * `clusterId`. `auctionData.collateral` (in SOL) fixes the deposit every
+ * bid must lock; leave it unset to let bidders size their own deposits.
  */
-export async function createAuctionWithProgram(wallet, auctionData, arciumPubkey) {
+export async function createAuctionWithProgram(
+  wallet,
+  auctionData,
+  clusterId = DEFAULT_CLUSTER_ID
+) {
   try {
     const program = await getProgram(wallet);
-    const auctionPDA = getAuctionPDA(wallet.publicKey, auctionData.itemName);
+    const index = await getNextAuctionIndex(program, wallet.publicKey);
+    const auctionPDA = getAuctionPDA(wallet.publicKey, index);
+
+    const params = {
+      itemName: auctionData.itemName,
+      description: auctionData.description,
+      minBid: new anchor.BN(auctionData.minimumBid * 1e9), // Convert SOL to lamports
+      startTime: null, // Open immediately
+      endTime: new anchor.BN(Math.floor(auctionData.endTime / 1000)), // Convert to seconds
+      maxBids: new anchor.BN(auctionData.maxBids ?? MAX_BIDS_PER_AUCTION),
+      collateral: new anchor.BN((auctionData.collateral ?? 0) * 1e9), // Fixed deposit in lamports
+      assetAmount: new anchor.BN(0), // No escrowed SPL asset
+      kind: { firstPrice: {} },
+      encryptedReserve: null, // No reserve price
+      quantity: new anchor.BN(1), // Single item
+      revealEndTime: null, // Sealed bids, no reveal phase
+      extensionWindow: new anchor.BN(0), // No soft close
+      extensionDuration: new anchor.BN(0),
+      maxExtension: new anchor.BN(0),
+      allowWithdrawals: false, // Bids are binding once placed
+      keeperBounty: new anchor.BN(0), // No reward for third-party finalizers
+      paymentWindow: new anchor.BN(0), // Bids are fully funded
+      defaultPolicy: { failAuction: {} },
+      revenueShares: [], // Pay the creator directly
+      allowlistRoot: null, // Public auction
+      bidGate: null, // No token or collection requirement
+    };
 
     const tx = await program.methods
-      .createAuction(
-        auctionData.itemName,
-        auctionData.description,
-        new anchor.BN(auctionData.minimumBid * 1e9), // Convert SOL to lamports
-        new anchor.BN(Math.floor(auctionData.endTime / 1000)), // Convert to seconds
-        Array.from(arciumPubkey) // Arcium MXE public key
-      )
+      .createAuction(params)
       .accounts({
+        config: getConfigPDA(),
+        creatorProfile: getCreatorProfilePDA(wallet.publicKey),
         auction: auctionPDA,
+        vault: getVaultPDA(auctionPDA),
+        quoteMint: null,
+        quoteVault: null,
+        assetMint: null,
+        assetVault: null,
+        creatorAssetAccount: null,
+        assetMetadata: null,
+        cluster: getClusterPDA(clusterId),
         creator: wallet.publicKey,
         systemProgram: SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
       })
       .rpc();
 
@@ -112,32 +290,41 @@ export async function createAuctionWithProgram(wallet, auctionData, arciumPubkey
 
 /**
  * Submit encrypted bid using deployed program
+ *
+ * `deposit` is the amount locked in escrow, in SOL. Private auctions also
+ * need the bidder's Merkle proof against the auction's allowlist root.
  */
 export async function submitBidWithProgram(
   wallet,
   auctionPDA,
   encryptedBid,
-  bidCount
+  deposit,
+  allowlistProof = []
 ) {
   try {
     const program = await getProgram(wallet);
-    const bidPDA = getBidPDA(
-      new PublicKey(auctionPDA),
-      wallet.publicKey,
-      bidCount
-    );
+    const auction = new PublicKey(auctionPDA);
+    const bidPDA = getBidPDA(auction, wallet.publicKey);
 
     const tx = await program.methods
       .submitBid(
-        Array.from(encryptedBid.ciphertext),     // Encrypted bid data
+        Buffer.from(encryptedBid.ciphertext),     // Encrypted bid data
         Array.from(encryptedBid.publicKey),       // x25519 public key
-        Array.from(encryptedBid.nonce)            // Encryption nonce
+        Array.from(encryptedBid.nonce),           // Encryption nonce
+        new anchor.BN(deposit * 1e9),             // Escrowed deposit in lamports
+        allowlistProof.map((node) => Array.from(node))
       )
       .accounts({
-        auction: new PublicKey(auctionPDA),
+        auction,
+        config: getConfigPDA(),
+        vault: getVaultPDA(auction),
         bid: bidPDA,
+        nonceRegistry: getNonceRegistryPDA(auction, encryptedBid.publicKey),
+        quoteVault: null,
+        bidderTokenAccount: null,
         bidder: wallet.publicKey,
         systemProgram: SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       })
       .rpc();
 
@@ -154,35 +341,66 @@ export async function submitBidWithProgram(
 }
 
 /**
- * Finalize auction using deployed program
+ * Request MPC finalization of an ended auction using deployed program
+ *
+ * Every bid of the auction is passed along so the Arcium cluster can
+ * compare them; the winner is recorded when the computation calls back
+ * into the program.
  */
-export async function finalizeAuctionWithProgram(
-  wallet,
-  auctionPDA,
-  winner,
-  winningBid,
-  computationId
-) {
+export async function requestFinalizationWithProgram(wallet, auctionPDA) {
   try {
     const program = await getProgram(wallet);
+    const auction = new PublicKey(auctionPDA);
+    const computationOffset = new anchor.BN(
+      crypto.getRandomValues(new Uint8Array(8)),
+      'le'
+    );
+    const computationAccount = getComputationPDA(computationOffset);
+    const mxeAccount = getArciumPDA('MXEAccount');
+
+    const bids = await program.account.bid.all([
+      { memcmp: { offset: 8, bytes: auction.toBase58() } },
+    ]);
 
     const tx = await program.methods
-      .finalizeAuction(
-        new PublicKey(winner),
-        new anchor.BN(winningBid * 1e9),
-        computationId
-      )
+      .requestFinalization(computationOffset)
       .accounts({
-        auction: new PublicKey(auctionPDA),
+        auction,
+        config: getConfigPDA(),
+        vault: getVaultPDA(auction),
         authority: wallet.publicKey,
+        signPdaAccount: getSignPDA(),
+        mxeAccount,
+        mempoolAccount: getArciumPDA('Mempool'),
+        executingPool: getArciumPDA('Execpool'),
+        computationAccount,
+        compDefAccount: getArciumPDA(
+          'ComputationDefinitionAccount',
+          toLeBytes(FINALIZE_AUCTION_COMP_DEF_OFFSET, 4)
+        ),
+        clusterAccount: await getMxeClusterPDA(mxeAccount),
+        poolAccount: ARCIUM_FEE_POOL_ACCOUNT,
+        clockAccount: ARCIUM_CLOCK_ACCOUNT,
+        systemProgram: SystemProgram.programId,
+        arciumProgram: ARCIUM_PROGRAM_ID,
       })
+      .remainingAccounts(
+        bids.map((bid) => ({
+          pubkey: bid.publicKey,
+          isWritable: false,
+          isSigner: false,
+        }))
+      )
       .rpc();
 
-    console.log('Auction finalized on-chain:', tx);
+    console.log('Auction finalization requested on-chain:', tx);
     
-    return { signature: tx };
+    return {
+      signature: tx,
+      computationAccount: computationAccount.toString(),
+    };
   } catch (error) {
-    console.error('Error finalizing auction:', error);
+    console.error('Error requesting finalization:', error);
     throw error;
   }
 }
@@ -203,6 +421,7 @@ export async function fetchAuctionData(auctionPDA) {
       endTime: auction.endTime.toNumber() * 1000,
       status: auction.status,
       bidCount: auction.bidCount.toNumber(),
+      maxBids: auction.maxBids.toNumber(),
       winner: auction.winner?.toString(),
       winningBid: auction.winningBid?.toNumber() / 1e9,
     };
@@ -220,64 +439,142 @@ const IDL = {
     {
       name: "createAuction",
       accounts: [
+        { name: "config", isMut: false, isSigner: false },
+        { name: "creatorProfile", isMut: true, isSigner: false },
         { name: "auction", isMut: true, isSigner: false },
+        { name: "vault", isMut: true, isSigner: false },
+        { name: "quoteMint", isMut: false, isSigner: false, isOptional: true },
+        { name: "quoteVault", isMut: true, isSigner: false, isOptional: true },
+        { name: "assetMint", isMut: false, isSigner: false, isOptional: true },
+        { name: "assetVault", isMut: true, isSigner: false, isOptional: true },
+        { name: "creatorAssetAccount", isMut: true, isSigner: false, isOptional: true },
+        { name: "assetMetadata", isMut: false, isSigner: false, isOptional: true },
+        { name: "cluster", isMut: false, isSigner: false, isOptional: true },
         { name: "creator", isMut: true, isSigner: true },
-        { name: "systemProgram", isMut: false, isSigner: false }
+        { name: "systemProgram", isMut: false, isSigner: false },
+        { name: "tokenProgram", isMut: false, isSigner: false },
+        { name: "associatedTokenProgram", isMut: false, isSigner: false }
       ],
       args: [
-        { name: "itemName", type: "string" },
-        { name: "description", type: "string" },
-        { name: "minBid", type: "u64" },
-        { name: "endTime", type: "i64" },
-        { name: "arciumMxePubkey", type: { array: ["u8", 32] } }
+        { name: "params", type: { defined: "CreateAuctionParams" } }
       ]
     },
     {
       name: "submitBid",
       accounts: [
         { name: "auction", isMut: true, isSigner: false },
+        { name: "config", isMut: false, isSigner: false },
+        { name: "vault", isMut: true, isSigner: false },
         { name: "bid", isMut: true, isSigner: false },
+        { name: "nonceRegistry", isMut: true, isSigner: false, isOptional: true },
+        { name: "quoteVault", isMut: true, isSigner: false, isOptional: true },
+        { name: "bidderTokenAccount", isMut: true, isSigner: false, isOptional: true },
         { name: "bidder", isMut: true, isSigner: true },
-        { name: "systemProgram", isMut: false, isSigner: false }
+        { name: "systemProgram", isMut: false, isSigner: false },
+        { name: "tokenProgram", isMut: false, isSigner: false }
       ],
       args: [
         { name: "encryptedBidData", type: { vec: "u8" } },
         { name: "bidderPubkey", type: { array: ["u8", 32] } },
-        { name: "nonce", type: { array: ["u8", 16] } }
+        { name: "nonce", type: { array: ["u8", 16] } },
+        { name: "deposit", type: "u64" },
+        { name: "allowlistProof", type: { vec: { array: ["u8", 32] } } }
       ]
     },
     {
-      name: "finalizeAuction",
+      name: "requestFinalization",
       accounts: [
         { name: "auction", isMut: true, isSigner: false },
-        { name: "authority", isMut: false, isSigner: true }
+        { name: "config", isMut: false, isSigner: false },
+        { name: "vault", isMut: true, isSigner: false },
+        { name: "authority", isMut: true, isSigner: true },
+        { name: "signPdaAccount", isMut: true, isSigner: false },
+        { name: "mxeAccount", isMut: false, isSigner: false },
+        { name: "mempoolAccount", isMut: true, isSigner: false },
+        { name: "executingPool", isMut: true, isSigner: false },
+        { name: "computationAccount", isMut: true, isSigner: false },
+        { name: "compDefAccount", isMut: false, isSigner: false },
+        { name: "clusterAccount", isMut: true, isSigner: false },
+        { name: "poolAccount", isMut: true, isSigner: false },
+        { name: "clockAccount", isMut: false, isSigner: false },
+        { name: "systemProgram", isMut: false, isSigner: false },
+        { name: "arciumProgram", isMut: false, isSigner: false }
       ],
       args: [
-        { name: "winnerPubkey", type: "publicKey" },
-        { name: "winningBidAmount", type: "u64" },
-        { name: "mpcComputationId", type: "string" }
+        { name: "computationOffset", type: "u64" }
       ]
     }
   ],
   accounts: [
+    {
+      name: "CreatorProfile",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "creator", type: "publicKey" },
+          { name: "auctionCount", type: "u64" },
+          { name: "bump", type: "u8" }
+        ]
+      }
+    },
     {
       name: "Auction",
       type: {
         kind: "struct",
         fields: [
           { name: "creator", type: "publicKey" },
+          { name: "index", type: "u64" },
           { name: "itemName", type: "string" },
           { name: "description", type: "string" },
           { name: "minBid", type: "u64" },
           { name: "endTime", type: "i64" },
           { name: "createdAt", type: "i64" },
+          { name: "startTime", type: "i64" },
           { name: "status", type: { defined: "AuctionStatus" } },
           { name: "bidCount", type: "u64" },
+          { name: "maxBids", type: "u64" },
           { name: "arciumMxePubkey", type: { array: ["u8", 32] } },
+          { name: "clusterId", type: { option: "u32" } },
+          { name: "collateral", type: "u64" },
+          { name: "quoteMint", type: { option: "publicKey" } },
+          { name: "kind", type: { defined: "AuctionKind" } },
+          { name: "encryptedReserve", type: { option: { defined: "EncryptedValue" } } },
+          { name: "quantity", type: "u64" },
+          { name: "unitsSold", type: "u64" },
+          { name: "revealEndTime", type: { option: "i64" } },
+          { name: "forfeitedDeposits", type: "u64" },
+          { name: "forfeitsClaimed", type: "bool" },
+          { name: "extensionWindow", type: "i64" },
+          { name: "extensionDuration", type: "i64" },
+          { name: "maxExtension", type: "i64" },
+          { name: "extendedBy", type: "i64" },
+          { name: "allowWithdrawals", type: "bool" },
+          { name: "feeBps", type: "u16" },
+          { name: "keeperFeeBps", type: "u16" },
+          { name: "keeperBounty", type: "u64" },
+          { name: "keeper", type: { option: "publicKey" } },
+          { name: "paymentWindow", type: "i64" },
+          { name: "defaultPolicy", type: { defined: "DefaultPolicy" } },
+          { name: "revenueShares", type: { vec: { defined: "RevenueShare" } } },
+          { name: "frozen", type: "bool" },
+          { name: "allowlistRoot", type: { option: { array: ["u8", 32] } } },
+          { name: "bidGate", type: { option: { defined: "BidGate" } } },
+          { name: "closedBidCount", type: "u64" },
           { name: "winner", type: { option: "publicKey" } },
           { name: "winningBid", type: { option: "u64" } },
+          { name: "clearingPrice", type: { option: "u64" } },
           { name: "mpcComputationId", type: { option: "string" } },
+          { name: "computationAccount", type: { option: "publicKey" } },
+          { name: "computationBids", type: { vec: { defined: "ComputationBid" } } },
+          { name: "finalizationDeadline", type: { option: "i64" } },
+          { name: "paymentDeadline", type: { option: "i64" } },
+          { name: "runnerUp", type: { option: "publicKey" } },
+          { name: "runnerUpBid", type: { option: "u64" } },
           { name: "finalizedAt", type: { option: "i64" } },
+          { name: "proceedsClaimed", type: "bool" },
+          { name: "assetMint", type: { option: "publicKey" } },
+          { name: "assetAmount", type: "u64" },
+          { name: "assetReleased", type: "bool" },
           { name: "bump", type: "u8" }
         ]
       }
@@ -293,12 +590,121 @@ const IDL = {
           { name: "x25519Pubkey", type: { array: ["u8", 32] } },
           { name: "nonce", type: { array: ["u8", 16] } },
           { name: "timestamp", type: "i64" },
+          { name: "deposit", type: "u64" },
+          { name: "settled", type: "bool" },
+          { name: "revealedAmount", type: { option: "u64" } },
+          { name: "revision", type: "u32" },
           { name: "bump", type: "u8" }
         ]
       }
     }
   ],
   types: [
+    {
+      name: "CreateAuctionParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "itemName", type: "string" },
+          { name: "description", type: "string" },
+          { name: "minBid", type: "u64" },
+          { name: "startTime", type: { option: "i64" } },
+          { name: "endTime", type: "i64" },
+          { name: "maxBids", type: "u64" },
+          { name: "collateral", type: "u64" },
+          { name: "assetAmount", type: "u64" },
+          { name: "kind", type: { defined: "AuctionKind" } },
+          { name: "encryptedReserve", type: { option: { defined: "EncryptedValue" } } },
+          { name: "quantity", type: "u64" },
+          { name: "revealEndTime", type: { option: "i64" } },
+          { name: "extensionWindow", type: "i64" },
+          { name: "extensionDuration", type: "i64" },
+          { name: "maxExtension", type: "i64" },
+          { name: "allowWithdrawals", type: "bool" },
+          { name: "keeperBounty", type: "u64" },
+          { name: "paymentWindow", type: "i64" },
+          { name: "defaultPolicy", type: { defined: "DefaultPolicy" } },
+          { name: "revenueShares", type: { vec: { defined: "RevenueShare" } } },
+          { name: "allowlistRoot", type: { option: { array: ["u8", 32] } } },
+          { name: "bidGate", type: { option: { defined: "BidGate" } } }
+        ]
+      }
+    },
+    {
+      name: "ComputationBid",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "bidder", type: "publicKey" },
+          { name: "deposit", type: "u64" },
+          { name: "allocatedQuantity", type: "u64" }
+        ]
+      }
+    },
+    {
+      name: "RevenueShare",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "recipient", type: "publicKey" },
+          { name: "bps", type: "u16" }
+        ]
+      }
+    },
+    {
+      name: "EncryptedValue",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "ciphertext", type: { array: ["u8", 32] } },
+          { name: "x25519Pubkey", type: { array: ["u8", 32] } },
+          { name: "nonce", type: { array: ["u8", 16] } }
+        ]
+      }
+    },
+    {
+      name: "AuctionKind",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "FirstPrice" },
+          { name: "SecondPrice" },
+          { name: "UniformPrice" },
+          { name: "CommitReveal" }
+        ]
+      }
+    },
+    {
+      name: "DefaultPolicy",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "FailAuction" },
+          { name: "OfferRunnerUp" }
+        ]
+      }
+    },
+    {
+      name: "BidGate",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Token",
+            fields: [
+              { name: "mint", type: "publicKey" },
+              { name: "minAmount", type: "u64" }
+            ]
+          },
+          {
+            name: "Collection",
+            fields: [
+              { name: "collection", type: "publicKey" }
+            ]
+          }
+        ]
+      }
+    },
     {
       name: "AuctionStatus",
       type: {
@@ -306,7 +712,13 @@ const IDL = {
         variants: [
           { name: "Active" },
           { name: "Finalized" },
-          { name: "Cancelled" }
+          { name: "Cancelled" },
+          { name: "Finalizing" },
+          { name: "ReserveNotMet" },
+          { name: "Upcoming" },
+          { name: "Failed" },
+          { name: "AwaitingPayment" },
+          { name: "Defaulted" }
         ]
       }
     }
@@ -316,9 +728,15 @@ const IDL = {
 export default {
   createAuctionWithProgram,
   submitBidWithProgram,
-  finalizeAuctionWithProgram,
+  requestFinalizationWithProgram,
   fetchAuctionData,
+  getConfigPDA,
+  getCreatorProfilePDA,
   getAuctionPDA,
+  getVaultPDA,
+  getClusterPDA,
   getBidPDA,
+  getNonceRegistryPDA,
+  getComputationPDA,
   PROGRAM_ID,
 };
\ No newline at end of file
@@ -114,19 +114,51 @@ export async function getMXEPublicKey(connection, programId) {
 }
 
 /**
- * Encrypt a bid amount using Arcium's encryption scheme
+ * Length of an encrypted bid: price, quantity and tag blocks of 32 bytes,
+ * matching `SEALED_BID_LEN` in the program
+ */
+export const SEALED_BID_LEN = 96;
+
+/**
+ * Compute the authentication tag a bid must carry, matching `bid_tag` in
+ * the program: the first 16 bytes of sha256(auction || bidder), read
+ * little-endian
+ *
+ * @param {PublicKey} auction - Auction account the bid is for
+ * @param {PublicKey} bidder - Wallet placing the bid
+ * @returns {Promise<BigInt>} Tag as a u128
+ */
+export async function computeBidTag(auction, bidder) {
+  const associatedData = new Uint8Array(64);
+  associatedData.set(auction.toBytes(), 0);
+  associatedData.set(bidder.toBytes(), 32);
+
+  const digest = new Uint8Array(await crypto.subtle.digest('SHA-256', associatedData));
+
+  let tag = 0n;
+  for (let i = 15; i >= 0; i--) {
+    tag = (tag << 8n) + BigInt(digest[i]);
+  }
+  return tag;
+}
+
+/**
+ * Encrypt a bid using Arcium's encryption scheme
  * 
  * Process:
  * 1. Generate ephemeral x25519 keypair
  * 2. Perform key exchange with MXE cluster
  * 3. Use shared secret to initialize Rescue cipher
- * 4. Encrypt bid amount
+ * 4. Encrypt the price, quantity and the tag binding the bid to its
+ *    auction and bidder, so the MPC discards it anywhere else
  * 
- * @param {BigInt} bidAmount - The bid amount in lamports
+ * @param {BigInt} bidAmount - The bid price per unit in lamports
+ * @param {Object} binding - { auction, bidder, quantity } the bid is for;
+ *   quantity defaults to 1 for single-item auctions
  * @param {Uint8Array} mxePublicKey - Optional MXE public key (generated if not provided)
  * @returns {Promise<Object>} { ciphertext, publicKey, nonce }
  */
-export async function encryptBid(bidAmount, mxePublicKey = null) {
+export async function encryptBid(bidAmount, { auction, bidder, quantity = 1n }, mxePublicKey = null) {
   if (!mxePublicKey) {
     mxePublicKey = await getMXEPublicKey();
   }
@@ -140,7 +172,11 @@ export async function encryptBid(bidAmount, mxePublicKey = null) {
   const nonce = new Uint8Array(16);
   crypto.getRandomValues(nonce);
   
-  const ciphertext = cipher.encrypt(bidAmount, nonce);
+  const tag = await computeBidTag(auction, bidder);
+  const ciphertext = new Uint8Array(SEALED_BID_LEN);
+  [bidAmount, BigInt(quantity), tag].forEach((block, i) => {
+    ciphertext.set(cipher.encrypt(block, nonce), i * 32);
+  });
   
   return {
     ciphertext: Array.from(ciphertext),
@@ -149,7 +185,7 @@ export async function encryptBid(bidAmount, mxePublicKey = null) {
     metadata: {
       algorithm: 'x25519-Rescue',
       timestamp: Date.now(),
-      version: '1.0.0',
+      version: '1.1.0',
     },
   };
 }
@@ -189,7 +225,7 @@ export function verifyEncryption(encryptedData) {
     return false;
   }
   
-  if (encryptedData.ciphertext.length !== 32) return false;
+  if (encryptedData.ciphertext.length !== SEALED_BID_LEN) return false;
   if (encryptedData.publicKey.length !== 32) return false;
   if (encryptedData.nonce.length !== 16) return false;
   
@@ -207,6 +243,7 @@ export function generateComputationId() {
 
 export default {
   encryptBid,
+  computeBidTag,
   decryptResult,
   getMXEPublicKey,
   verifyEncryption,